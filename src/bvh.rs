@@ -0,0 +1,163 @@
+use crate::{
+    matters::{bounds::BoundingBox, Intersection, Shape},
+    ray::Ray,
+    vector::Point,
+};
+
+/// NOTES:
+/// 1. a bounding volume hierarchy is a binary tree of boxes over a list of shapes, each node box contains the boxes of it's children.
+/// 2. a ray which misses a node box can't hit anything inside it, so most of the shapes are never tested and the cost is logarithmic.
+/// 3. nodes are split at the median of the shape centers along the axis where the centers are the most spread (median split).
+/// 4. the tree stores indices into the list it was built from, so it has to be built again when the list changes.
+/// 5. shapes with infinite bounds (like planes) can't be put in a box, they are tested against every ray.
+#[derive(Debug)]
+pub struct Bvh {
+    root: Option<BvhNode>,
+    // indices of shapes with infinite bounds
+    unbounded: Vec<usize>,
+    // number of shapes the tree was built for
+    len: usize,
+}
+
+#[derive(Debug)]
+enum BvhNode {
+    Leaf {
+        bounds: BoundingBox,
+        objects: Vec<usize>,
+    },
+    Branch {
+        bounds: BoundingBox,
+        left: Box<BvhNode>,
+        right: Box<BvhNode>,
+    },
+}
+
+// a node with this many shapes or less is not split any further
+const MAX_LEAF_SIZE: usize = 4;
+
+// index of a shape with it's bounds and the center of the bounds
+struct BuildItem {
+    index: usize,
+    bounds: BoundingBox,
+    centroid: Point,
+}
+
+fn axis_value(point: &Point, axis: usize) -> f64 {
+    match axis {
+        0 => point.0,
+        1 => point.1,
+        _ => point.2,
+    }
+}
+
+fn build_node(items: &mut [BuildItem]) -> BvhNode {
+    let mut bounds = BoundingBox::empty();
+    let mut centroid_bounds = BoundingBox::empty();
+    for item in items.iter() {
+        bounds.merge(&item.bounds);
+        centroid_bounds.add_point(&item.centroid);
+    }
+    if items.len() <= MAX_LEAF_SIZE {
+        return BvhNode::Leaf {
+            bounds,
+            objects: items.iter().map(|item| item.index).collect(),
+        };
+    }
+    let axis = centroid_bounds.largest_axis();
+    let middle = items.len() / 2;
+    // only the median has to be in place, both halves can stay unsorted
+    items.select_nth_unstable_by(middle, |a, b| {
+        axis_value(&a.centroid, axis).total_cmp(&axis_value(&b.centroid, axis))
+    });
+    let (left, right) = items.split_at_mut(middle);
+    BvhNode::Branch {
+        bounds,
+        left: Box::new(build_node(left)),
+        right: Box::new(build_node(right)),
+    }
+}
+
+impl Bvh {
+    pub fn build(objects: &[Box<dyn Shape>]) -> Self {
+        let mut unbounded = Vec::new();
+        let mut items = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            let bounds = object.parent_space_bounds();
+            if bounds.is_finite() {
+                let centroid = bounds.centroid();
+                items.push(BuildItem {
+                    index,
+                    bounds,
+                    centroid,
+                });
+            } else if !bounds.is_empty() {
+                unbounded.push(index);
+            }
+        }
+        let root = if items.is_empty() {
+            None
+        } else {
+            Some(build_node(&mut items))
+        };
+        Self {
+            root,
+            unbounded,
+            len: objects.len(),
+        }
+    }
+
+    // number of shapes the tree was built for
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // depth of the tree, a single leaf has a depth of 1
+    pub fn depth(&self) -> usize {
+        fn node_depth(node: &BvhNode) -> usize {
+            match node {
+                BvhNode::Leaf { .. } => 1,
+                BvhNode::Branch { left, right, .. } => 1 + node_depth(left).max(node_depth(right)),
+            }
+        }
+        self.root.as_ref().map(node_depth).unwrap_or(0)
+    }
+
+    // intersections of the ray with the shapes (the same list the tree was built from), not sorted
+    pub fn intersect<'a>(&self, objects: &'a [Box<dyn Shape>], ray: &Ray) -> Vec<Intersection<'a>> {
+        let mut xs = self
+            .unbounded
+            .iter()
+            .flat_map(|&index| objects[index].intersect(ray))
+            .collect::<Vec<Intersection>>();
+        let mut stack = self.root.iter().collect::<Vec<&BvhNode>>();
+        while let Some(node) = stack.pop() {
+            match node {
+                BvhNode::Leaf {
+                    bounds,
+                    objects: indices,
+                } => {
+                    if bounds.intersects(ray) {
+                        for &index in indices {
+                            xs.extend(objects[index].intersect(ray));
+                        }
+                    }
+                }
+                BvhNode::Branch {
+                    bounds,
+                    left,
+                    right,
+                } => {
+                    if bounds.intersects(ray) {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                }
+            }
+        }
+        xs
+    }
+}
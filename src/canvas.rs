@@ -183,6 +183,123 @@ impl Canvas {
     pub fn aspect_ratio(&self) -> f64 {
         self.height as f64 / self.width as f64
     }
+
+    // reads a plain (P3) or binary (P6) ppm image, colors are scaled from 0..=maxval to 0..=1
+    pub fn from_ppm(content: &[u8]) -> Result<Self, String> {
+        let mut reader = PpmReader { content, index: 0 };
+        let magic = reader.token().ok_or("missing magic number")?;
+        let binary = match magic {
+            b"P3" => false,
+            b"P6" => true,
+            _ => {
+                return Err(format!(
+                    "unsupported magic number {:?}",
+                    String::from_utf8_lossy(magic)
+                ))
+            }
+        };
+        let width = reader.number()?;
+        let height = reader.number()?;
+        let maxval = reader.number()?;
+        if width == 0 || height == 0 || maxval == 0 || maxval > 65535 {
+            return Err(format!("bad header {} {} {}", width, height, maxval));
+        }
+        // in a binary image exactly one whitespace byte separates the header from the pixels
+        if binary {
+            reader.index += 1;
+        }
+        // a made up header could ask for more pixels than there is memory, every value takes at least a byte so the rest of the image must be long enough
+        let values = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or(format!("image of {} x {} is too big", width, height))?;
+        let value_size = if binary && maxval >= 256 { 2 } else { 1 };
+        let left = content.len().saturating_sub(reader.index) as u64;
+        if values.saturating_mul(value_size) > left {
+            return Err(format!(
+                "image of {} x {} needs more than the {} bytes left",
+                width, height, left
+            ));
+        }
+        let mut canvas = Canvas::new(width, height);
+        for y in 0..height as usize {
+            for x in 0..width as usize {
+                let mut rgb = [0.0; 3];
+                for channel in rgb.iter_mut() {
+                    let value = if binary {
+                        reader.byte_value(maxval)?
+                    } else {
+                        reader.number()?
+                    };
+                    if value > maxval {
+                        return Err(format!("color value {} is more than {}", value, maxval));
+                    }
+                    *channel = value as f64 / maxval as f64;
+                }
+                canvas.write_pixel((x, y), &Color::from(rgb));
+            }
+        }
+        Ok(canvas)
+    }
+
+    pub fn from_ppm_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        Self::from_ppm(&std::fs::read(path)?)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+    }
+}
+
+// walks the bytes of a ppm image, header tokens are separated by any whitespace and `#` starts a comment till the end of the line
+struct PpmReader<'a> {
+    content: &'a [u8],
+    index: usize,
+}
+
+impl<'a> PpmReader<'a> {
+    fn token(&mut self) -> Option<&'a [u8]> {
+        // skip whitespace and comments before the token
+        loop {
+            match self.content.get(self.index)? {
+                b'#' => {
+                    while self.content.get(self.index).is_some_and(|&b| b != b'\n') {
+                        self.index += 1;
+                    }
+                }
+                b if b.is_ascii_whitespace() => self.index += 1,
+                _ => break,
+            }
+        }
+        let start = self.index;
+        while self
+            .content
+            .get(self.index)
+            .is_some_and(|b| !b.is_ascii_whitespace() && *b != b'#')
+        {
+            self.index += 1;
+        }
+        Some(&self.content[start..self.index])
+    }
+
+    fn number(&mut self) -> Result<u64, String> {
+        let token = self.token().ok_or("unexpected end of image")?;
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|token| token.parse().ok())
+            .ok_or(format!(
+                "{:?} is not a number",
+                String::from_utf8_lossy(token)
+            ))
+    }
+
+    // one byte per value, two bytes (most significant first) when maxval doesn't fit in a byte
+    fn byte_value(&mut self, maxval: u64) -> Result<u64, String> {
+        let size = if maxval < 256 { 1 } else { 2 };
+        let bytes = self
+            .content
+            .get(self.index..self.index + size)
+            .ok_or("unexpected end of image")?;
+        self.index += size;
+        Ok(bytes.iter().fold(0, |value, &b| (value << 8) | b as u64))
+    }
 }
 
 impl AsRef<Color> for Color {
@@ -1,9 +1,12 @@
 use world::World;
 
+mod bvh;
 mod canvas;
 mod matrix;
 mod matters;
+mod obj_parser;
 mod projectile;
+mod random;
 mod ray;
 #[cfg(test)]
 mod tests;
@@ -18,6 +21,7 @@ fn main() {
     // sphere.sphere_with_lighting_to_canvas();
     let mut world = World::new();
     // world.world_to_ppm();
+    // world.many_spheres_to_ppm();
     // working on this
     world.shadow_dog_to_ppm();
 }
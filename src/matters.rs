@@ -1,43 +1,137 @@
-use std::cmp::Ordering;
+use std::{borrow::Cow, cmp::Ordering, fmt::Debug};
 
 use crate::{
     matrix::Matrix,
+    matters::{bounds::BoundingBox, material::Material},
     ray::Ray,
     vector::{Point, Vec4},
 };
 
+pub mod background;
+pub mod bounds;
 pub mod camera;
+pub mod cone;
+pub mod csg;
+pub mod cube;
+pub mod cylinder;
+pub mod group;
 pub mod light;
 pub mod material;
+pub mod motion;
+pub mod pattern;
+pub mod perlin;
+pub mod plane;
+pub mod sampling;
+pub mod smooth_triangle;
 pub mod sphere;
+pub mod texture;
+pub mod triangle;
 
-pub trait Shape: Default + Clone {
+// anything smaller than this is treated as zero, like when checking if a ray is parallel to a surface
+pub const EPSILON: f64 = 0.00001;
+
+/// NOTES:
+/// 1. every shape is defined in it's own object space (unit sphere at origin, xz plane, etc) and placed in the world with it's transformation.
+/// 2. a shape only has to implement `local_intersect` and `local_normal_at` which work in object space,
+///    `intersect` and `normal_at` take care of converting between world space and object space.
+/// 3. the trait is object safe so a world can hold different kind of shapes as `Box<dyn Shape>`.
+/// 4. shapes are `Send + Sync` so the camera can render a world from many threads at once.
+/// 5. a shape can move while the camera's shutter is open (see motion.rs), `transformation_at` gives it's transformation at the time of the ray.
+///    every intersection keeps the time of the ray so the normal is computed where the shape was when it was hit.
+pub trait Shape: Debug + Send + Sync {
     // create a shape with a transformation
-    fn new(transformation_matrix: Matrix) -> Self {
+    fn new(transformation_matrix: Matrix) -> Self
+    where
+        Self: Sized + Default,
+    {
         let mut shape = Self::default();
         shape.set_transformation(transformation_matrix);
         shape
     }
-    //transform the ray from world space coordinate to object space coordinate by appling inverse of shape transformation (self.transformation) to the ray.
-    // intersects a Shape with ray
-    fn intersect(&self, ray: &Ray) -> Vec<Intersection<Self>>;
-    // finds the normal at a point on a shape
-    fn normal_at(&self, point: &Point) -> Vec4;
+    // transformation which takes the shape from object space to world space
+    fn transformation(&self) -> &Matrix;
+    // transformation at some time, only moving shapes have a different one at different times
+    fn transformation_at(&self, _time: f64) -> Cow<'_, Matrix> {
+        Cow::Borrowed(self.transformation())
+    }
     // set transformation to the shape
     fn set_transformation(&mut self, transformation_matrix: Matrix);
+    // material used to shade the shape
+    fn material(&self) -> &Material;
+    // intersects the shape with a ray which is already in object space
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>>;
+    // box containing the shape in object space
+    fn bounds(&self) -> BoundingBox;
+    // finds the normal at a point (in object space) on the shape
+    // hit is the intersection which gave the point, shapes like smooth triangles need it's u/v to interpolate the normal
+    fn local_normal_at(&self, point: &Point, hit: Option<&Intersection>) -> Vec4;
+
+    // box containing the shape in the space of it's parent (the world or the group it's in)
+    fn parent_space_bounds(&self) -> BoundingBox {
+        self.bounds().transform(self.transformation())
+    }
+    //transform the ray from world space coordinate to object space coordinate by appling inverse of shape transformation to the ray.
+    // intersects a Shape with ray
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let transformed_ray =
+            ray.transform(self.transformation_at(ray.time).inverse_4x4().unwrap());
+        let mut xs = self.local_intersect(&transformed_ray);
+        for intersection in xs.iter_mut() {
+            intersection.time = ray.time;
+        }
+        xs
+    }
+    // finds the normal at a point (in world space) on a shape
+    // the groups in hit.parents (if any) are walked to bring the point and normal between world and object space
+    fn normal_at(&self, point: &Point, hit: Option<&Intersection>) -> Vec4 {
+        let parents = hit.map(|hit| hit.parents.as_slice()).unwrap_or(&[]);
+        let time = hit.map(|hit| hit.time).unwrap_or(0.0);
+        let object_point = self.world_to_object(point, parents, time);
+        let object_normal = self.local_normal_at(&object_point, hit);
+        self.normal_to_world(object_normal, parents, time)
+    }
+    // brings a point from world space to object space, parents are the groups containing the shape from innermost to outermost
+    // time is when the shape (and it's groups) are, for the ones that move
+    fn world_to_object(&self, point: &Point, parents: &[&dyn Shape], time: f64) -> Point {
+        // the outermost group is the closest to world space so it's transformation is undone first
+        let group_point = parents.iter().rev().fold(point.clone(), |point, parent| {
+            parent.transformation_at(time).inverse_4x4().unwrap() * point
+        });
+        self.transformation_at(time).inverse_4x4().unwrap() * group_point
+    }
+    // brings a normal from object space to world space, parents are the groups containing the shape from innermost to outermost
+    fn normal_to_world(&self, normal: Vec4, parents: &[&dyn Shape], time: f64) -> Vec4 {
+        // normally we only have to multiply transformation matrix to bring object space normal to world space
+        // not in this case, use the transpose of inverse so the normal stays perpendicular to the surface
+        let to_parent_space = |transformation: &Matrix, normal: Vec4| {
+            let mut normal = transformation.inverse_4x4().unwrap().transpose() * normal;
+            normal.3 = 0.0;
+            normal.normalize()
+        };
+        let normal = to_parent_space(&self.transformation_at(time), normal);
+        parents.iter().fold(normal, |normal, parent| {
+            to_parent_space(&parent.transformation_at(time), normal)
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
-pub struct Intersection<T: Clone> {
+pub struct Intersection<'a> {
     pub distance: f64,
-    pub object: T,
+    pub object: &'a dyn Shape,
+    // barycentric coordinates of the point on a triangle, relative to it's second and third vertex
+    pub uv: Option<(f64, f64)>,
+    // groups containing the object, from the innermost to the outermost
+    pub parents: Vec<&'a dyn Shape>,
+    // time of the ray which made the intersection
+    pub time: f64,
 }
 
-pub struct PrerareComputation<T: Clone> {
+pub struct PrerareComputation<'a> {
     // distance at which ray intersected
     pub distance: f64,
     // object which is intersected
-    pub object: T,
+    pub object: &'a dyn Shape,
     // point at which ray intersected
     pub point: Point,
     // opposite of ray direction (eye vector)
@@ -48,24 +142,138 @@ pub struct PrerareComputation<T: Clone> {
     pub inside: bool,
     // for shadows
     pub over_point: Point,
+    // ray direction reflected around the normal, direction of the reflected ray
+    pub reflectv: Vec4,
+    // refractive index of the material the ray is leaving (n1) and entering (n2)
+    pub n1: f64,
+    pub n2: f64,
+    // just below the surface, refracted rays start from here
+    pub under_point: Point,
+    // time of the ray, the reflected, refracted and shadow rays are shot at the same time
+    pub time: f64,
+    // groups (and moving shapes) the object is in, innermost first, patterns need them to find the object space of the point
+    pub parents: Vec<&'a dyn Shape>,
 }
 
-pub trait Intersectable: Clone {
-    fn hits(intersections: &[Intersection<Self>]) -> Option<Intersection<Self>>;
+impl<'a> Intersection<'a> {
+    pub fn new(distance: f64, object: &'a dyn Shape) -> Self {
+        Self {
+            distance,
+            object,
+            uv: None,
+            parents: Vec::new(),
+            time: 0.0,
+        }
+    }
 
-    fn prepare_computation(
-        intersection: &Intersection<Self>,
+    pub fn with_uv(distance: f64, object: &'a dyn Shape, u: f64, v: f64) -> Self {
+        Self {
+            distance,
+            object,
+            uv: Some((u, v)),
+            parents: Vec::new(),
+            time: 0.0,
+        }
+    }
+
+    pub fn hits(intersections: &[Intersection<'a>]) -> Option<Intersection<'a>> {
+        // if distance is negative than the object is behind the ray so exclude those intersections in hits
+        intersections
+            .iter()
+            .filter(|i| i.distance >= 0.0)
+            .min()
+            .cloned()
+    }
+
+    // xs are all the intersections of the ray (sorted), they are needed to know which objects the hit is inside of
+    pub fn prepare_computation(
+        intersection: &Intersection<'a>,
         ray: &Ray,
-    ) -> PrerareComputation<Self>;
+        xs: &[Intersection<'a>],
+    ) -> PrerareComputation<'a> {
+        let point = ray.position(intersection.distance);
+        let normalv = intersection.object.normal_at(&point, Some(intersection));
+        let eyev = -ray.direction.clone();
+        let (normalv, inside) = if normalv.dot(&eyev) < 0.0 {
+            (-normalv, true)
+        } else {
+            (normalv, false)
+        };
+        let over_point = &point + &(0.000000001 * &normalv);
+        let under_point = &point + &(-0.000000001 * &normalv);
+        let reflectv = ray.direction.reflect(&normalv);
+        let (n1, n2) = Self::refractive_indices(intersection, xs);
+        PrerareComputation {
+            distance: intersection.distance,
+            normalv,
+            object: intersection.object,
+            point,
+            eyev,
+            inside,
+            over_point,
+            reflectv,
+            n1,
+            n2,
+            under_point,
+            time: ray.time,
+            parents: intersection.parents.clone(),
+        }
+    }
+
+    // walks the intersections up to the hit keeping a list of objects the ray is inside of (containers),
+    // an intersection with an object in the list leaves it otherwise enters it.
+    // the last container before the hit gives n1 and the last container after it gives n2, 1 (vacuum) if there is none.
+    fn refractive_indices(hit: &Intersection<'a>, xs: &[Intersection<'a>]) -> (f64, f64) {
+        let last_index = |containers: &[&dyn Shape]| {
+            containers
+                .last()
+                .map(|object| object.material().refractive_index)
+                .unwrap_or(1.0)
+        };
+        let mut containers: Vec<&dyn Shape> = Vec::new();
+        let mut n1 = 1.0;
+        for i in xs {
+            let is_hit = i.distance == hit.distance && std::ptr::addr_eq(i.object, hit.object);
+            if is_hit {
+                n1 = last_index(&containers);
+            }
+            match containers
+                .iter()
+                .position(|object| std::ptr::addr_eq(*object, i.object))
+            {
+                Some(position) => {
+                    containers.remove(position);
+                }
+                None => containers.push(i.object),
+            }
+            if is_hit {
+                return (n1, last_index(&containers));
+            }
+        }
+        (n1, 1.0)
+    }
 }
 
-impl<T: Clone> Intersection<T> {
-    pub fn new(distance: f64, object: T) -> Self {
-        Self { distance, object }
+impl PrerareComputation<'_> {
+    // schlick's approximation of the fresnel effect, the fraction of the light which is reflected
+    pub fn schlick(&self) -> f64 {
+        let mut cos = self.eyev.dot(&self.normalv);
+        // total internal reflection can only happen going into a material with a smaller refractive index
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n.powi(2) * (1.0 - cos.powi(2));
+            if sin2_t > 1.0 {
+                return 1.0;
+            }
+            // when n1 > n2 use the cosine of the refracted angle instead
+            cos = (1.0 - sin2_t).sqrt();
+        }
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
     }
 }
 
-impl<T: Clone> Ord for Intersection<T> {
+impl Ord for Intersection<'_> {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.distance == other.distance {
             Ordering::Equal
@@ -77,22 +285,16 @@ impl<T: Clone> Ord for Intersection<T> {
     }
 }
 
-impl<T: Clone> PartialOrd for Intersection<T> {
+impl PartialOrd for Intersection<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.distance == other.distance {
-            Some(Ordering::Equal)
-        } else if self.distance < other.distance {
-            Some(Ordering::Less)
-        } else {
-            Some(Ordering::Greater)
-        }
+        Some(self.cmp(other))
     }
 }
 
-impl<T: Clone> PartialEq for Intersection<T> {
+impl PartialEq for Intersection<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.distance == other.distance
     }
 }
 
-impl<T: Clone> Eq for Intersection<T> {}
+impl Eq for Intersection<'_> {}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use crate::{
+    canvas::Color,
+    vector::{Point, Vec4},
+};
+
+use super::{
+    pattern::Pattern,
+    texture::{CubeMap, UvMapping, UvPattern},
+};
+
+/// NOTES:
+/// 1. the background is the color of a ray which doesn't hit anything, it only depends on the direction of the ray
+///    so it looks infinitely far away (it doesn't move when the camera moves, only when it turns).
+/// 2. the vertical gradient blends from `bottom` (ray pointing straight down) to `top` (straight up), like a simple sky.
+/// 3. a cube map is looked up at the point where the direction leaves a unit cube around the camera, one image per face.
+///    it's transformation turns the whole skybox around, moving it does nothing since it's infinitely far away.
+/// 4. an equirectangular image covers the whole sphere of directions in one image (u around the y axis, v from bottom to top),
+///    that's the layout most panoramas and hdr environment maps come in. it's seen from inside, u grows to the right when looking along +z.
+#[derive(Debug, Clone)]
+pub enum Background {
+    Solid(Color),
+    VerticalGradient { top: Color, bottom: Color },
+    CubeMap(CubeMap),
+    Equirectangular(Arc<dyn UvPattern>),
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Background::Solid(Color::black())
+    }
+}
+
+impl Background {
+    pub fn color_for(&self, direction: &Vec4) -> Color {
+        let direction = direction.normalize();
+        match self {
+            Background::Solid(color) => color.clone(),
+            Background::VerticalGradient { top, bottom } => {
+                let t = 0.5 * (direction.1 + 1.0);
+                &((1.0 - t) * bottom) + &(t * top)
+            }
+            Background::CubeMap(cube_map) => {
+                // turn the direction into the cube map's own space, only it's rotation (and scaling) matters for a direction
+                let direction = &cube_map.transformation().inverse_4x4().unwrap() * &direction;
+                // scale the direction so it ends on the surface of the unit cube
+                let largest = direction
+                    .0
+                    .abs()
+                    .max(direction.1.abs())
+                    .max(direction.2.abs());
+                cube_map.pattern_at(&Point::new(
+                    direction.0 / largest,
+                    direction.1 / largest,
+                    direction.2 / largest,
+                ))
+            }
+            Background::Equirectangular(image) => {
+                let (u, v) =
+                    UvMapping::Spherical.map(&Point::new(direction.0, direction.1, direction.2));
+                // the spherical mapping is for a sphere seen from outside, from inside u has to go the other way or the image is mirrored
+                image.uv_pattern_at(1.0 - u, v)
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{matrix::Matrix, ray::Ray, vector::Point};
+
+use super::EPSILON;
+
+/// NOTES:
+/// 1. an axis aligned box given by it's smallest (min) and largest (max) corner.
+/// 2. shapes report their bounds in object space, `transform` gives the box which contains the transformed box (it can only grow).
+/// 3. infinite shapes like planes have infinite bounds, an empty box has min at +infinity and max at -infinity so adding anything to it gives that thing back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Default for BoundingBox {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+// distances at which the ray enters and leaves the slab between min and max for one axis
+fn check_axis(origin: f64, direction: f64, min: f64, max: f64) -> (f64, f64) {
+    let tmin_numerator = min - origin;
+    let tmax_numerator = max - origin;
+    let (tmin, tmax) = if direction.abs() >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (
+            tmin_numerator * f64::INFINITY,
+            tmax_numerator * f64::INFINITY,
+        )
+    };
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+impl BoundingBox {
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(
+            Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        )
+    }
+
+    pub fn infinite() -> Self {
+        Self::new(
+            Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.0 > self.max.0 || self.min.1 > self.max.1 || self.min.2 > self.max.2
+    }
+
+    // a box is finite if none of it's corners is at infinity
+    pub fn is_finite(&self) -> bool {
+        !self.is_empty()
+            && [
+                self.min.0, self.min.1, self.min.2, self.max.0, self.max.1, self.max.2,
+            ]
+            .iter()
+            .all(|c| c.is_finite())
+    }
+
+    // grow the box so that it contains the point
+    pub fn add_point(&mut self, point: &Point) {
+        self.min = Point::new(
+            self.min.0.min(point.0),
+            self.min.1.min(point.1),
+            self.min.2.min(point.2),
+        );
+        self.max = Point::new(
+            self.max.0.max(point.0),
+            self.max.1.max(point.1),
+            self.max.2.max(point.2),
+        );
+    }
+
+    // grow the box so that it contains the other box
+    pub fn merge(&mut self, other: &BoundingBox) {
+        if other.is_empty() {
+            return;
+        }
+        self.add_point(&other.min);
+        self.add_point(&other.max);
+    }
+
+    pub fn contains_point(&self, point: &Point) -> bool {
+        (self.min.0..=self.max.0).contains(&point.0)
+            && (self.min.1..=self.max.1).contains(&point.1)
+            && (self.min.2..=self.max.2).contains(&point.2)
+    }
+
+    pub fn contains_box(&self, other: &BoundingBox) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    pub fn centroid(&self) -> Point {
+        Point::new(
+            (self.min.0 + self.max.0) / 2.0,
+            (self.min.1 + self.max.1) / 2.0,
+            (self.min.2 + self.max.2) / 2.0,
+        )
+    }
+
+    // area of the box surface, used to compare how good a split is
+    pub fn surface_area(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let (dx, dy, dz) = (
+            self.max.0 - self.min.0,
+            self.max.1 - self.min.1,
+            self.max.2 - self.min.2,
+        );
+        2.0 * (dx * dy + dy * dz + dz * dx)
+    }
+
+    // 0 for x, 1 for y and 2 for z
+    pub fn largest_axis(&self) -> usize {
+        let (dx, dy, dz) = (
+            self.max.0 - self.min.0,
+            self.max.1 - self.min.1,
+            self.max.2 - self.min.2,
+        );
+        if dx >= dy && dx >= dz {
+            0
+        } else if dy >= dz {
+            1
+        } else {
+            2
+        }
+    }
+
+    // box containing all the corners of this box after applying the transformation
+    pub fn transform(&self, transformation: &Matrix) -> Self {
+        if self.is_empty() {
+            return Self::empty();
+        }
+        // infinity times zero in the matrix multiplication gives NaN, so infinite boxes stay infinite
+        if !self.is_finite() {
+            return Self::infinite();
+        }
+        let mut transformed = Self::empty();
+        for x in [self.min.0, self.max.0] {
+            for y in [self.min.1, self.max.1] {
+                for z in [self.min.2, self.max.2] {
+                    transformed.add_point(&(transformation * &Point::new(x, y, z)));
+                }
+            }
+        }
+        transformed
+    }
+
+    // slab method like the cube, but with the box's own min and max
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let (xtmin, xtmax) = check_axis(ray.origin.0, ray.direction.0, self.min.0, self.max.0);
+        let (ytmin, ytmax) = check_axis(ray.origin.1, ray.direction.1, self.min.1, self.max.1);
+        let (ztmin, ztmax) = check_axis(ray.origin.2, ray.direction.2, self.min.2, self.max.2);
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+        // boxes behind the ray still count, intersections behind the ray origin are needed (like for refraction)
+        tmin <= tmax
+    }
+}
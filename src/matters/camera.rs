@@ -3,8 +3,49 @@
 // - vsize is the canvas’s vertical size (in pixels).
 // - field_of_view is an angle that describes how much the camera can see. When the field of view is small, the view will be “zoomed in,” magnifying a smaller area of the scene.
 // - transform is a matrix describing how the world should be oriented relative to the camera. This is usually a view transformation like you implemented in the previous section.
+// - exposure multiplies every color before it's written to the canvas, lights given in physical units (see `Light::with_power`) need a lot less (or more) than 1.
+//   one stop brighter doubles it, `Camera::set_exposure_stops` works in stops.
+// - samples is the number of rays shot through every pixel, they are placed by sample_pattern and their colors are weighted by filter (see sampling.rs).
+//   with 1 sample (the default) the ray goes through the pixel's center.
+// - adaptive (off by default) starts every pixel with samples rays and adds more, a few at a time, only where the colors vary. render_with_heatmap shows where they went.
+// - aperture is the radius of the lens, 0 (the default) is a pinhole and everything is sharp. with a bigger aperture the rays start anywhere on the lens
+//   (shaped by aperture_shape) and meet again at focal_distance, so only things at that distance are in focus. it needs many samples per pixel to look smooth.
+// - shutter_open and shutter_close are the times the shutter opens and closes, every ray is shot at some time between them.
+//   moving shapes (see motion.rs) are smeared along their path, both are 0 by default so nothing moves.
+// - projection is how the canvas is mapped to rays (see `Projection`), perspective by default.
+//   the lens (aperture) only blurs a perspective view, the other projections are always sharp.
+// - threads is the number of worker threads render uses, every pixel is computed the same way so the image does not depend on it.
 
-use crate::{canvas::Canvas, matrix::Matrix, ray::Ray, vector::Point, world::World};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+    canvas::{Canvas, Color},
+    matrix::Matrix,
+    matters::sampling::{AdaptiveSampling, ApertureShape, Filter, PixelSamples, SamplePattern},
+    random,
+    ray::Ray,
+    vector::{Point, Vec4},
+    world::World,
+};
+
+// rays added at a time to a pixel which needs more, it stops as soon as they agree
+const ADAPTIVE_BATCH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Projection {
+    // a pinhole camera, far things look smaller, field_of_view is the angle across the longer side of the canvas
+    #[default]
+    Perspective,
+    // parallel rays, things look the same size at any distance (like technical drawings), size is how much (in world units) the longer side of the canvas shows
+    Orthographic {
+        size: f64,
+    },
+    // the angle of a ray from the view direction grows with it's distance from the center of the canvas,
+    // field_of_view is the angle across the longer side (PI for a 180 deg fisheye)
+    Fisheye,
+    // the whole sphere around the camera, longitude goes across the canvas (360 deg) and latitude down it (180 deg), field_of_view is not used
+    Equirectangular,
+}
 
 pub struct Camera {
     pub hsize: usize,
@@ -15,6 +56,25 @@ pub struct Camera {
     pub pixel_size: f64,
     pub half_width: f64,
     pub half_height: f64,
+    // 1 keeps the colors as they are
+    pub exposure: f64,
+    // rays per pixel
+    pub samples: usize,
+    pub sample_pattern: SamplePattern,
+    pub filter: Filter,
+    // radius of the lens, 0 for a pinhole
+    pub aperture: f64,
+    pub aperture_shape: ApertureShape,
+    // distance from the camera to the plane in focus
+    pub focal_distance: f64,
+    // every ray is shot at some time from shutter_open to shutter_close
+    pub shutter_open: f64,
+    pub shutter_close: f64,
+    // adds rays only where the image needs them, samples is then the number every pixel gets first
+    pub adaptive: Option<AdaptiveSampling>,
+    pub projection: Projection,
+    // defaults to the number of cores, 1 renders on the calling thread
+    pub threads: usize,
 }
 
 impl Camera {
@@ -27,6 +87,20 @@ impl Camera {
             pixel_size: 0.0,
             half_width: 0.0,
             half_height: 0.0,
+            exposure: 1.0,
+            samples: 1,
+            sample_pattern: SamplePattern::default(),
+            filter: Filter::default(),
+            adaptive: None,
+            aperture: 0.0,
+            aperture_shape: ApertureShape::default(),
+            focal_distance: 1.0,
+            shutter_open: 0.0,
+            shutter_close: 0.0,
+            projection: Projection::default(),
+            threads: std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
         };
         let half_view = (field_of_view / 2.0).tan();
         let aspect = hsize as f64 / vsize as f64;
@@ -40,11 +114,38 @@ impl Camera {
         camera
     }
 
+    // 0 stops keeps the colors as they are, every stop doubles (or halves when negative) them
+    pub fn set_exposure_stops(&mut self, stops: f64) {
+        self.exposure = 2f64.powf(stops);
+    }
+
     // gives a ray starting for a pixel on camera and passing through a point (px,py) on canvas
     pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray {
+        let seed = random::seed(&[px as u64, py as u64]);
         // the offset from the edge of the canvas to the pixel's center
-        let xoffset = (px as f64 + 0.5) * self.pixel_size;
-        let yoffset = (py as f64 + 0.5) * self.pixel_size;
+        self.ray_for_canvas_point(px as f64 + 0.5, py as f64 + 0.5, self.lens_point(seed))
+            .with_time(self.shutter_time(seed))
+    }
+
+    // a point on the lens (of radius 1), the same seed gives the same point
+    fn lens_point(&self, seed: u64) -> (f64, f64) {
+        self.aperture_shape
+            .sample(random::unit_f64(seed), random::unit_f64(seed ^ 1))
+    }
+
+    // some time while the shutter is open, the same seed gives the same time
+    fn shutter_time(&self, seed: u64) -> f64 {
+        self.shutter_open + (self.shutter_close - self.shutter_open) * random::unit_f64(seed ^ 2)
+    }
+
+    // like ray_for_pixel, x and y are (in pixels) from the edge of the canvas and can be anywhere in a pixel
+    // lens is where the ray starts on the lens, scaled by the aperture so it doesn't matter for a pinhole
+    pub fn ray_for_canvas_point(&self, x: f64, y: f64, lens: (f64, f64)) -> Ray {
+        if self.projection != Projection::Perspective {
+            return self.projected_ray(x, y);
+        }
+        let xoffset = x * self.pixel_size;
+        let yoffset = y * self.pixel_size;
 
         //  the untransformed coordinates of the pixel in world space.
         //  (remember that the camera looks toward -z, so +x is to the *left*.)
@@ -53,22 +154,224 @@ impl Camera {
 
         //  using the camera matrix, transform the canvas point and the origin,
         //  and then compute the ray's direction vector.
-        //  (remember that the canvas is at z=-1)
-        let pixel = self.transform.inverse_4x4().unwrap() * Point::new(world_x, world_y, -1.0);
-        let origin = self.transform.inverse_4x4().unwrap() * Point::origin();
-        let direction = (&pixel - &origin).normalize();
+        //  (remember that the canvas is at z=-1, scaled out to the focal plane it's the same direction)
+        let inverse = self.transform.inverse_4x4().unwrap();
+        // the ray through the center of the lens goes on to the focal plane, every ray from the lens meets it there
+        let focus = Point::new(
+            world_x * self.focal_distance,
+            world_y * self.focal_distance,
+            -self.focal_distance,
+        );
+        // a pinhole (no aperture) always starts at the center
+        let lens = Point::new(lens.0 * self.aperture, lens.1 * self.aperture, 0.0);
+        let focus = &inverse * &focus;
+        let origin = &inverse * &lens;
+        let direction = (&focus - &origin).normalize();
+        Ray::new(origin, direction)
+    }
+
+    // rays of the projections other than perspective
+    fn projected_ray(&self, x: f64, y: f64) -> Ray {
+        // from the center of the canvas, -1 to 1 across the longer side (+u is to the *left*, like for perspective)
+        let half = self.hsize.max(self.vsize) as f64 / 2.0;
+        let u = (self.hsize as f64 / 2.0 - x) / half;
+        let v = (self.vsize as f64 / 2.0 - y) / half;
+        let (origin, direction) = match self.projection {
+            Projection::Orthographic { size } => (
+                Point::new(u * size / 2.0, v * size / 2.0, 0.0),
+                Vec4::new(0.0, 0.0, -1.0),
+            ),
+            Projection::Fisheye => {
+                let r = (u * u + v * v).sqrt();
+                let theta = r * self.field_of_view / 2.0;
+                // at the center theta is 0 and the ray goes straight ahead, no side to lean to
+                let (dx, dy) = if r > 0.0 { (u / r, v / r) } else { (0.0, 0.0) };
+                (
+                    Point::origin(),
+                    Vec4::new(theta.sin() * dx, theta.sin() * dy, -theta.cos()),
+                )
+            }
+            Projection::Equirectangular => {
+                let longitude = (x / self.hsize as f64 - 0.5) * 2.0 * std::f64::consts::PI;
+                let latitude = (0.5 - y / self.vsize as f64) * std::f64::consts::PI;
+                (
+                    Point::origin(),
+                    Vec4::new(
+                        -longitude.sin() * latitude.cos(),
+                        latitude.sin(),
+                        -longitude.cos() * latitude.cos(),
+                    ),
+                )
+            }
+            Projection::Perspective => {
+                unreachable!("perspective rays are made by ray_for_canvas_point")
+            }
+        };
+        let inverse = self.transform.inverse_4x4().unwrap();
+        let origin = &inverse * &origin;
+        let direction = (&inverse * &direction).normalize();
         Ray::new(origin, direction)
     }
+
+    // color of a pixel, the weighted average of all the rays shot through (and around) it
+    pub fn color_for_pixel(&self, world: &World, px: usize, py: usize) -> Color {
+        let mut samples = PixelSamples::default();
+        self.sample_pixel(world, px, py, self.samples, 0, &mut samples);
+        samples.color()
+    }
+
+    // shoots `count` more rays for the pixel, every pass gets different offsets
+    fn sample_pixel(
+        &self,
+        world: &World,
+        px: usize,
+        py: usize,
+        count: usize,
+        pass: u64,
+        samples: &mut PixelSamples,
+    ) {
+        let (cx, cy) = (px as f64 + 0.5, py as f64 + 0.5);
+        // a grid puts every pass on the same spots, more passes have to land somewhere else
+        let sample_pattern = match self.sample_pattern {
+            SamplePattern::Grid if pass > 0 => SamplePattern::Stratified,
+            sample_pattern => sample_pattern,
+        };
+        let offsets = if count <= 1 && pass == 0 {
+            vec![(0.0, 0.0)]
+        } else {
+            sample_pattern.offsets(
+                count,
+                self.filter.radius(),
+                random::seed(&[px as u64, py as u64, pass]),
+            )
+        };
+        for (i, (dx, dy)) in offsets.into_iter().enumerate() {
+            let seed = random::seed(&[px as u64, py as u64, pass, i as u64]);
+            let ray = self
+                .ray_for_canvas_point(cx + dx, cy + dy, self.lens_point(seed))
+                .with_time(self.shutter_time(seed));
+            let color = world.color_at(&ray);
+            samples.add(&color, self.filter.weight(dx, dy));
+        }
+    }
+
+    // true if the rays through the pixel don't agree or the pixel stands out from a neighbour
+    fn needs_more_samples(
+        &self,
+        pixels: &[Vec<PixelSamples>],
+        x: usize,
+        y: usize,
+        threshold: f64,
+    ) -> bool {
+        let pixel = &pixels[y][x];
+        if pixel.variance() > threshold * threshold {
+            return true;
+        }
+        let color = pixel.color();
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        neighbours.iter().any(|(nx, ny)| {
+            pixels
+                .get(*ny)
+                .and_then(|row| row.get(*nx))
+                .is_some_and(|neighbour| {
+                    let (r, g, b) = (&neighbour.color() - &color).rgb();
+                    r.abs().max(g.abs()).max(b.abs()) > threshold
+                })
+        })
+    }
+
+    // runs `row` for every row on the worker threads, the rows come back in order
+    fn rows<T: Send>(&self, row: impl Fn(usize) -> Vec<T> + Sync) -> Vec<Vec<T>> {
+        let threads = self.threads.clamp(1, self.vsize.max(1));
+        if threads == 1 {
+            return (0..self.vsize).map(row).collect();
+        }
+        // workers take the next row which is not rendered yet, so a slow part of the image doesn't keep one worker busy while others wait
+        let next_row = AtomicUsize::new(0);
+        let mut rows = std::thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut rows = Vec::new();
+                        loop {
+                            let y = next_row.fetch_add(1, Ordering::Relaxed);
+                            if y >= self.vsize {
+                                break rows;
+                            }
+                            rows.push((y, row(y)));
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect::<Vec<(usize, Vec<T>)>>()
+        });
+        rows.sort_by_key(|(y, _)| *y);
+        rows.into_iter().map(|(_, row)| row).collect()
+    }
+
     pub fn render(&self, world: &World) -> Canvas {
-        let mut image = Canvas::new(self.hsize as u64, self.vsize as u64);
+        self.render_with_heatmap(world).0
+    }
 
-        for y in 0..self.vsize {
-            for x in 0..self.hsize {
-                let ray = self.ray_for_pixel(x, y);
-                let color = world.color_at_sphere(&ray);
-                image.write_pixel((x, y), &color);
+    // the image and a heatmap of how many rays every pixel got, from blue (the fewest) to red (max_samples)
+    pub fn render_with_heatmap(&self, world: &World) -> (Canvas, Canvas) {
+        // every pixel gets the same number of rays first
+        let first_pass = self.rows(|y| {
+            (0..self.hsize)
+                .map(|x| {
+                    let mut samples = PixelSamples::default();
+                    self.sample_pixel(world, x, y, self.samples, 0, &mut samples);
+                    samples
+                })
+                .collect()
+        });
+        let (pixels, max_samples) = match self.adaptive {
+            None => (first_pass, self.samples.max(1)),
+            Some(adaptive) => {
+                // pixels only look at their neighbours from the first pass, so the rows can still be done in any order
+                let pixels = self.rows(|y| {
+                    (0..self.hsize)
+                        .map(|x| {
+                            let mut samples = first_pass[y][x].clone();
+                            if !self.needs_more_samples(&first_pass, x, y, adaptive.threshold) {
+                                return samples;
+                            }
+                            // a batch at a time until the rays agree or there are max_samples of them
+                            let mut pass = 1;
+                            while samples.count() < adaptive.max_samples {
+                                let count =
+                                    ADAPTIVE_BATCH.min(adaptive.max_samples - samples.count());
+                                self.sample_pixel(world, x, y, count, pass, &mut samples);
+                                pass += 1;
+                                if samples.variance() <= adaptive.threshold * adaptive.threshold {
+                                    break;
+                                }
+                            }
+                            samples
+                        })
+                        .collect()
+                });
+                (pixels, adaptive.max_samples.max(1))
+            }
+        };
+
+        let mut image = Canvas::new(self.hsize as u64, self.vsize as u64);
+        let mut heatmap = Canvas::new(self.hsize as u64, self.vsize as u64);
+        for (y, row) in pixels.iter().enumerate() {
+            for (x, samples) in row.iter().enumerate() {
+                image.write_pixel((x, y), &(self.exposure * samples.color()));
+                let heat = (samples.count() as f64 / max_samples as f64).min(1.0);
+                heatmap.write_pixel((x, y), &Color::new(heat, 0.0, 1.0 - heat));
             }
         }
-        image
+        (image, heatmap)
     }
 }
@@ -0,0 +1,127 @@
+use crate::{
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{
+    bounds::BoundingBox, cylinder::check_cap, material::Material, Intersection, Shape, EPSILON,
+};
+
+/// NOTES:
+/// 1. in object space the cone is a double napped cone centered on the y axis, the radius at any y is |y| so the tips meet at the origin.
+/// 2. like the cylinder `minimum` and `maximum` truncate it on the y axis and `closed` adds caps,
+///    the cap at y has a radius of |y|.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cone {
+    // identity matrix as default transformation, it can be changed so making it public
+    pub transformation: Matrix,
+    pub material: Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self {
+            transformation: Matrix::identity_4x4(),
+            material: Material::default(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+impl Cone {
+    // intersections with the end caps of a closed cone
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        // caps only matter if the cone is closed and the ray is not parallel to them
+        if !self.closed || ray.direction.1.abs() < EPSILON {
+            return;
+        }
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.1) / ray.direction.1;
+            if check_cap(ray, t, y.abs()) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+
+    // adds the intersection at distance t if it is within the truncated part of the cone
+    fn push_if_in_bounds<'a>(
+        &'a self,
+        ray: &Ray,
+        t: f64,
+        intersections: &mut Vec<Intersection<'a>>,
+    ) {
+        let y = ray.origin.1 + t * ray.direction.1;
+        if self.minimum < y && y < self.maximum {
+            intersections.push(Intersection::new(t, self));
+        }
+    }
+}
+
+impl Shape for Cone {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections = Vec::with_capacity(4);
+        let Vec4(dx, dy, dz, _) = ray.direction;
+        let Point(ox, oy, oz, _) = ray.origin;
+        let a = dx * dx - dy * dy + dz * dz;
+        let b = 2.0 * ox * dx - 2.0 * oy * dy + 2.0 * oz * dz;
+        let c = ox * ox - oy * oy + oz * oz;
+        if a.abs() < EPSILON {
+            // the ray is parallel to one of the halves, so it hits the other half only once
+            if b.abs() >= EPSILON {
+                self.push_if_in_bounds(ray, -c / (2.0 * b), &mut intersections);
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant >= 0.0 {
+                let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+                let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+                self.push_if_in_bounds(ray, t0.min(t1), &mut intersections);
+                self.push_if_in_bounds(ray, t0.max(t1), &mut intersections);
+            }
+        }
+        self.intersect_caps(ray, &mut intersections);
+        intersections
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        // the radius is the largest at the end which is the farthest from the tip
+        let limit = self.minimum.abs().max(self.maximum.abs());
+        BoundingBox::new(
+            Point::new(-limit, self.minimum, -limit),
+            Point::new(limit, self.maximum, limit),
+        )
+    }
+
+    fn local_normal_at(&self, point: &Point, _hit: Option<&Intersection>) -> Vec4 {
+        let Point(x, y, z, _) = *point;
+        // square of the distance from the y axis
+        let dist = x * x + z * z;
+        if dist < y * y && y >= self.maximum - EPSILON {
+            Vec4::new(0.0, 1.0, 0.0)
+        } else if dist < y * y && y <= self.minimum + EPSILON {
+            Vec4::new(0.0, -1.0, 0.0)
+        } else {
+            // the normal points away from the y axis and along the slope, towards the tip
+            let ny = if y > 0.0 { -dist.sqrt() } else { dist.sqrt() };
+            Vec4::new(x, ny, z)
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{bounds::BoundingBox, material::Material, Intersection, Shape};
+
+/// NOTES:
+/// 1. constructive solid geometry combines two shapes (left and right) with an operation, the result is a shape with a solid inside.
+/// 2. all the intersections of both children are sorted and walked in order, keeping track of whether the ray is inside the left and the right shape.
+///    an intersection is kept only if it is on the surface of the combined shape.
+///    - union: surface of the left shape outside the right one, and of the right shape outside the left one.
+///    - intersection: surface of the left shape inside the right one, and of the right shape inside the left one.
+///    - difference: surface of the left shape outside the right one, and of the right shape inside the left one (the right one carves the left).
+/// 3. like a group it has no surface of it's own, it adds itself to the `parents` of the kept intersections so the normals use the csg transformation.
+/// 4. the children can be groups or other csg shapes, an intersection belongs to a child if the child is the object or one of it's parents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOperation {
+    // lhit is true if the left shape was hit, inl and inr tell if the ray is inside the left and right shape at that point
+    pub fn intersection_allowed(&self, lhit: bool, inl: bool, inr: bool) -> bool {
+        match self {
+            CsgOperation::Union => (lhit && !inr) || (!lhit && !inl),
+            CsgOperation::Intersection => (lhit && inr) || (!lhit && inl),
+            CsgOperation::Difference => (lhit && !inr) || (!lhit && inl),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Csg {
+    // identity matrix as default transformation, it can be changed so making it public
+    pub transformation: Matrix,
+    // not used for shading, both children have their own material
+    pub material: Material,
+    pub operation: CsgOperation,
+    left: Box<dyn Shape>,
+    right: Box<dyn Shape>,
+    // bounds of both children in csg space
+    bounds: BoundingBox,
+}
+
+impl Csg {
+    pub fn combine(operation: CsgOperation, left: Box<dyn Shape>, right: Box<dyn Shape>) -> Self {
+        let mut bounds = left.parent_space_bounds();
+        bounds.merge(&right.parent_space_bounds());
+        Self {
+            transformation: Matrix::identity_4x4(),
+            material: Material::default(),
+            operation,
+            left,
+            right,
+            bounds,
+        }
+    }
+
+    pub fn union(left: Box<dyn Shape>, right: Box<dyn Shape>) -> Self {
+        Self::combine(CsgOperation::Union, left, right)
+    }
+
+    pub fn intersection(left: Box<dyn Shape>, right: Box<dyn Shape>) -> Self {
+        Self::combine(CsgOperation::Intersection, left, right)
+    }
+
+    pub fn difference(left: Box<dyn Shape>, right: Box<dyn Shape>) -> Self {
+        Self::combine(CsgOperation::Difference, left, right)
+    }
+
+    pub fn left(&self) -> &dyn Shape {
+        self.left.as_ref()
+    }
+
+    pub fn right(&self) -> &dyn Shape {
+        self.right.as_ref()
+    }
+
+    // keeps the intersections which are on the surface of the combined shape, xs has to be sorted
+    pub fn filter_intersections<'a>(&self, xs: Vec<Intersection<'a>>) -> Vec<Intersection<'a>> {
+        // the ray starts outside of both children
+        let mut inl = false;
+        let mut inr = false;
+        let mut result = Vec::new();
+        for intersection in xs {
+            let lhit = includes(self.left(), &intersection);
+            if self.operation.intersection_allowed(lhit, inl, inr) {
+                result.push(intersection);
+            }
+            // every intersection enters or leaves the child it belongs to
+            if lhit {
+                inl = !inl;
+            } else {
+                inr = !inr;
+            }
+        }
+        result
+    }
+}
+
+// true if the intersection is on the shape, or on a shape inside it (when the shape is a group or csg)
+fn includes(shape: &dyn Shape, intersection: &Intersection) -> bool {
+    std::ptr::addr_eq(intersection.object, shape)
+        || intersection
+            .parents
+            .iter()
+            .any(|parent| std::ptr::addr_eq(*parent, shape))
+}
+
+impl Shape for Csg {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.bounds.clone()
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        if !self.bounds.intersects(ray) {
+            return vec![];
+        }
+        let mut xs = self.left.intersect(ray);
+        xs.extend(self.right.intersect(ray));
+        xs.sort();
+        let mut xs = self.filter_intersections(xs);
+        for intersection in xs.iter_mut() {
+            intersection.parents.push(self);
+        }
+        xs
+    }
+
+    fn local_normal_at(&self, _point: &Point, _hit: Option<&Intersection>) -> Vec4 {
+        unreachable!("a csg has no surface, normals are always computed on it's children")
+    }
+}
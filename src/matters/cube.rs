@@ -0,0 +1,91 @@
+use crate::{
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{bounds::BoundingBox, material::Material, Intersection, Shape, EPSILON};
+
+/// NOTES:
+/// 1. in object space the cube is axis aligned and goes from -1 to 1 on every axis (like the unit sphere it has a "radius" of 1).
+/// 2. intersections are found with the slab method, every pair of opposite faces is a slab
+///    and the ray is inside the cube only where it is inside all three slabs at the same time.
+/// 3. the normal is the axis of the face the point lies on i.e the component with the largest absolute value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cube {
+    // identity matrix as default transformation, it can be changed so making it public
+    pub transformation: Matrix,
+    pub material: Material,
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Self {
+            transformation: Matrix::identity_4x4(),
+            material: Material::default(),
+        }
+    }
+}
+
+// distances at which the ray enters and leaves the slab between -1 and 1 for one axis
+fn check_axis(origin: f64, direction: f64) -> (f64, f64) {
+    let tmin_numerator = -1.0 - origin;
+    let tmax_numerator = 1.0 - origin;
+    // a ray parallel to the slab is either always inside it or never, infinity takes care of both
+    let (tmin, tmax) = if direction.abs() >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (
+            tmin_numerator * f64::INFINITY,
+            tmax_numerator * f64::INFINITY,
+        )
+    };
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+impl Shape for Cube {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let (xtmin, xtmax) = check_axis(ray.origin.0, ray.direction.0);
+        let (ytmin, ytmax) = check_axis(ray.origin.1, ray.direction.1);
+        let (ztmin, ztmax) = check_axis(ray.origin.2, ray.direction.2);
+        // the ray enters the cube when it has entered every slab and leaves it when it leaves any one of them
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+        if tmin > tmax {
+            return vec![];
+        }
+        vec![Intersection::new(tmin, self), Intersection::new(tmax, self)]
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+
+    fn local_normal_at(&self, point: &Point, _hit: Option<&Intersection>) -> Vec4 {
+        let Point(x, y, z, _) = *point;
+        let maxc = x.abs().max(y.abs()).max(z.abs());
+        if maxc == x.abs() {
+            Vec4::new(x, 0.0, 0.0)
+        } else if maxc == y.abs() {
+            Vec4::new(0.0, y, 0.0)
+        } else {
+            Vec4::new(0.0, 0.0, z)
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{bounds::BoundingBox, material::Material, Intersection, Shape, EPSILON};
+
+/// NOTES:
+/// 1. in object space the cylinder has a radius of 1 and is centered on the y axis.
+/// 2. by default it is infinitely long, `minimum` and `maximum` truncate it on the y axis (both limits are exclusive).
+/// 3. a truncated cylinder is hollow unless it is `closed`, then it gets caps at `minimum` and `maximum`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cylinder {
+    // identity matrix as default transformation, it can be changed so making it public
+    pub transformation: Matrix,
+    pub material: Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Self {
+            transformation: Matrix::identity_4x4(),
+            material: Material::default(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+// checks if the intersection at distance t is within radius (from the y axis) of the ray, used for caps
+pub(super) fn check_cap(ray: &Ray, t: f64, radius: f64) -> bool {
+    let x = ray.origin.0 + t * ray.direction.0;
+    let z = ray.origin.2 + t * ray.direction.2;
+    x * x + z * z <= radius * radius
+}
+
+impl Cylinder {
+    // intersections with the end caps of a closed cylinder
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        // caps only matter if the cylinder is closed and the ray is not parallel to them
+        if !self.closed || ray.direction.1.abs() < EPSILON {
+            return;
+        }
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.1) / ray.direction.1;
+            if check_cap(ray, t, 1.0) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+impl Shape for Cylinder {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections = Vec::with_capacity(2);
+        let Vec4(dx, dy, dz, _) = ray.direction;
+        let Point(ox, oy, oz, _) = ray.origin;
+        let a = dx * dx + dz * dz;
+        // a ray parallel to the y axis can only hit the caps
+        if a.abs() >= EPSILON {
+            let b = 2.0 * ox * dx + 2.0 * oz * dz;
+            let c = ox * ox + oz * oz - 1.0;
+            let discriminant = b * b - 4.0 * a * c;
+            // the ray does not hit the (infinite) cylinder at all, so it can't hit the caps either
+            if discriminant < 0.0 {
+                return intersections;
+            }
+            let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+            for t in [t0.min(t1), t0.max(t1)] {
+                let y = oy + t * dy;
+                if self.minimum < y && y < self.maximum {
+                    intersections.push(Intersection::new(t, self));
+                }
+            }
+        }
+        self.intersect_caps(ray, &mut intersections);
+        intersections
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point::new(-1.0, self.minimum, -1.0),
+            Point::new(1.0, self.maximum, 1.0),
+        )
+    }
+
+    fn local_normal_at(&self, point: &Point, _hit: Option<&Intersection>) -> Vec4 {
+        let Point(x, y, z, _) = *point;
+        // square of the distance from the y axis
+        let dist = x * x + z * z;
+        if dist < 1.0 && y >= self.maximum - EPSILON {
+            Vec4::new(0.0, 1.0, 0.0)
+        } else if dist < 1.0 && y <= self.minimum + EPSILON {
+            Vec4::new(0.0, -1.0, 0.0)
+        } else {
+            Vec4::new(x, 0.0, z)
+        }
+    }
+}
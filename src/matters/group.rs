@@ -0,0 +1,102 @@
+use crate::{
+    bvh::Bvh,
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{bounds::BoundingBox, material::Material, Intersection, Shape};
+
+/// NOTES:
+/// 1. a group is a shape made of other shapes (children), it has no surface of it's own.
+/// 2. the group transformation applies to all of it's children, the ray is brought into the group space first and then each child brings it into it's own object space.
+/// 3. a child does not know about it's group, instead the group adds itself to the `parents` of every intersection of it's children,
+///    so the normal at a hit can walk the full chain of groups back to world space.
+/// 4. the bounds of the children are cached when they are added (so children are only added with `add_child`),
+///    a ray which misses the bounds never gets tested against the children. `build_bvh` goes further for groups with many children like meshes.
+#[derive(Debug)]
+pub struct Group {
+    // identity matrix as default transformation, it can be changed so making it public
+    pub transformation: Matrix,
+    // not used for shading, every child has it's own material
+    pub material: Material,
+    children: Vec<Box<dyn Shape>>,
+    // bounds of all the children in group space
+    bounds: BoundingBox,
+    bvh: Option<Bvh>,
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self {
+            transformation: Matrix::identity_4x4(),
+            material: Material::default(),
+            children: Vec::new(),
+            bounds: BoundingBox::empty(),
+            bvh: None,
+        }
+    }
+}
+
+impl Group {
+    pub fn add_child(&mut self, child: Box<dyn Shape>) {
+        self.bounds.merge(&child.parent_space_bounds());
+        self.children.push(child);
+        // the tree does not know about the new child
+        self.bvh = None;
+    }
+
+    pub fn children(&self) -> &[Box<dyn Shape>] {
+        &self.children
+    }
+
+    // builds a bounding volume hierarchy over the children, do it after all the children are added
+    pub fn build_bvh(&mut self) {
+        self.bvh = Some(Bvh::build(&self.children));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl Shape for Group {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.bounds.clone()
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        if !self.bounds.intersects(ray) {
+            return vec![];
+        }
+        let mut xs = match self.bvh.as_ref() {
+            Some(bvh) => bvh.intersect(&self.children, ray),
+            None => self
+                .children
+                .iter()
+                .flat_map(|child| child.intersect(ray))
+                .collect::<Vec<Intersection>>(),
+        };
+        for intersection in xs.iter_mut() {
+            intersection.parents.push(self);
+        }
+        xs.sort();
+        xs
+    }
+
+    fn local_normal_at(&self, _point: &Point, _hit: Option<&Intersection>) -> Vec4 {
+        unreachable!("a group has no surface, normals are always computed on it's children")
+    }
+}
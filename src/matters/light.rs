@@ -1,17 +1,260 @@
-use crate::{canvas::Color, vector::Point};
+use crate::{
+    canvas::Color,
+    random,
+    vector::{Point, Vec4},
+};
 
-// a point light source
+/// NOTES:
+/// 1. a point light sends light from a single point, so a point is either lit or in shadow and shadows have sharp edges.
+/// 2. an area light is a rectangle from `corner` along `uvec` and `vvec`, split into usteps x vsteps cells.
+///    a point on the light is taken from every cell, the point being lit is shaded with each of them and the results are averaged.
+///    points which can see only some of the cells are partly lit, that's the soft edge (penumbra) of the shadow.
+/// 3. with `jitter` the point is anywhere in the cell instead of it's center, so the penumbra looks like noise instead of bands.
+///    the jitter is seeded with the point being lit so the same scene always renders the same image.
+/// 4. a spot light is a point light which only shines in a cone around `direction`, fully inside `inner_angle` and fading out (smoothly) till `outer_angle`.
+/// 5. a directional light (like the sun) is so far away that it's light comes from the same `direction` everywhere,
+///    it has no position and shadow rays towards it go on to infinity.
+/// 6. lighting and shadows only use `light_vectors`, which gives the direction and distance to every sample of any kind of light.
+/// 7. by default a light is as bright at any distance, `falloff` makes it dimmer the further away the point is.
+///    within `radius` a light with falloff has all of it's `intensity`, so a point right next to it doesn't get an infinite amount of light.
+/// 8. `with_power` gives the intensity in physical units (watts, or lumens) with inverse square falloff,
+///    those scenes are usually much brighter (or darker) than 0..1 and the camera `exposure` brings them back.
 #[derive(Debug, Clone)]
 pub struct Light {
+    // center of an area light, not used by a directional light
     pub position: Point,
     pub intensity: Color,
+    pub kind: LightKind,
+    pub falloff: Falloff,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Falloff {
+    #[default]
+    None,
+    // intensity goes down with the distance
+    Linear {
+        radius: f64,
+    },
+    // intensity goes down with the square of the distance, like real lights
+    InverseSquare {
+        radius: f64,
+    },
+}
+
+impl Falloff {
+    // how much of the intensity is left at the distance, 1 within the radius
+    pub fn attenuation(&self, distance: f64) -> f64 {
+        match *self {
+            Falloff::None => 1.0,
+            Falloff::Linear { radius } => 1.0 / (distance / radius).max(1.0),
+            Falloff::InverseSquare { radius } => 1.0 / (distance / radius).max(1.0).powi(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum LightKind {
+    Point,
+    Area {
+        corner: Point,
+        // size of one cell along each edge of the rectangle
+        uvec: Vec4,
+        usteps: usize,
+        vvec: Vec4,
+        vsteps: usize,
+        jitter: bool,
+    },
+    Spot {
+        // where the spot points, normalized
+        direction: Vec4,
+        // half angles of the cone (in radians)
+        inner_angle: f64,
+        outer_angle: f64,
+    },
+    Directional {
+        // where the light goes, normalized
+        direction: Vec4,
+    },
 }
 
 impl Light {
+    // a point light
     pub fn new(position: Point, intensity: Color) -> Self {
         Self {
             position,
             intensity,
+            falloff: Falloff::None,
+            kind: LightKind::Point,
+        }
+    }
+
+    // full_uvec and full_vvec are the edges of the rectangle, jitter is on
+    pub fn area(
+        corner: Point,
+        full_uvec: Vec4,
+        usteps: usize,
+        full_vvec: Vec4,
+        vsteps: usize,
+        intensity: Color,
+    ) -> Self {
+        let usteps = usteps.max(1);
+        let vsteps = vsteps.max(1);
+        let position = &(&corner + &(0.5 * &full_uvec)) + &(0.5 * &full_vvec);
+        Self {
+            position,
+            intensity,
+            falloff: Falloff::None,
+            kind: LightKind::Area {
+                corner,
+                uvec: (1.0 / usteps as f64) * &full_uvec,
+                usteps,
+                vvec: (1.0 / vsteps as f64) * &full_vvec,
+                vsteps,
+                jitter: true,
+            },
+        }
+    }
+
+    pub fn spot(
+        position: Point,
+        direction: Vec4,
+        inner_angle: f64,
+        outer_angle: f64,
+        intensity: Color,
+    ) -> Self {
+        Self {
+            position,
+            intensity,
+            falloff: Falloff::None,
+            kind: LightKind::Spot {
+                direction: direction.normalize(),
+                inner_angle,
+                // an outer angle smaller than the inner one would have no fading edge
+                outer_angle: outer_angle.max(inner_angle),
+            },
+        }
+    }
+
+    pub fn directional(direction: Vec4, intensity: Color) -> Self {
+        Self {
+            position: Point::origin(),
+            intensity,
+            falloff: Falloff::None,
+            kind: LightKind::Directional {
+                direction: direction.normalize(),
+            },
+        }
+    }
+
+    pub fn with_falloff(mut self, falloff: Falloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    // the light sends out `power` (in watts, lumens, ...) evenly in all directions and falls off with the inverse square of the distance,
+    // the color it had is kept as the tint. a directional light has no distance to fall off with, `power` is what reaches every point
+    pub fn with_power(mut self, power: f64) -> Self {
+        if let LightKind::Directional { .. } = self.kind {
+            self.intensity = power * &self.intensity;
+            return self;
+        }
+        let radius = match self.falloff {
+            Falloff::InverseSquare { radius } => radius,
+            _ => 1.0,
+        };
+        // power spread over the sphere around the light, at the distance where the falloff starts
+        self.intensity = (power / (4.0 * std::f64::consts::PI * radius * radius)) * &self.intensity;
+        self.falloff = Falloff::InverseSquare { radius };
+        self
+    }
+
+    // how much of the intensity reaches a point `distance` away, a directional light (infinitely far away) never falls off
+    pub fn attenuation(&self, distance: f64) -> f64 {
+        if distance.is_infinite() {
+            return 1.0;
+        }
+        self.falloff.attenuation(distance)
+    }
+
+    // turns the jitter of an area light on or off, a point light has none
+    pub fn with_jitter(mut self, on: bool) -> Self {
+        if let LightKind::Area { jitter, .. } = &mut self.kind {
+            *jitter = on;
+        }
+        self
+    }
+
+    // normalized vectors from the point to every sample of the light with the distance to it (infinity for a directional light)
+    pub fn light_vectors(&self, point: &Point) -> Vec<(Vec4, f64)> {
+        match &self.kind {
+            LightKind::Directional { direction } => vec![(-direction.clone(), f64::INFINITY)],
+            _ => self
+                .samples(point)
+                .iter()
+                .map(|sample| {
+                    let v = sample - point;
+                    (v.normalize(), v.magnitude())
+                })
+                .collect(),
+        }
+    }
+
+    // how much of the light goes along light_vector (from the point to the light), only a spot light has less than all of it
+    pub fn cone_factor(&self, light_vector: &Vec4) -> f64 {
+        match &self.kind {
+            LightKind::Spot {
+                direction,
+                inner_angle,
+                outer_angle,
+            } => {
+                let cos = (-light_vector.clone()).dot(direction);
+                let (cos_inner, cos_outer) = (inner_angle.cos(), outer_angle.cos());
+                if cos >= cos_inner {
+                    1.0
+                } else if cos <= cos_outer {
+                    0.0
+                } else {
+                    // smoothstep between the outer and inner cone
+                    let t = (cos - cos_outer) / (cos_inner - cos_outer);
+                    t * t * (3.0 - 2.0 * t)
+                }
+            }
+            _ => 1.0,
+        }
+    }
+
+    // points on the light used to light `point`, one for every cell of an area light
+    // a directional light has no points, see `light_vectors`
+    pub fn samples(&self, point: &Point) -> Vec<Point> {
+        match &self.kind {
+            LightKind::Point | LightKind::Spot { .. } => vec![self.position.clone()],
+            LightKind::Directional { .. } => vec![],
+            LightKind::Area {
+                corner,
+                uvec,
+                usteps,
+                vvec,
+                vsteps,
+                jitter,
+            } => {
+                let seed = random::seed(&[point.0.to_bits(), point.1.to_bits(), point.2.to_bits()]);
+                let mut samples = Vec::with_capacity(usteps * vsteps);
+                for v in 0..*vsteps {
+                    for u in 0..*usteps {
+                        let (ju, jv) = if *jitter {
+                            let cell = random::seed(&[seed, u as u64, v as u64]);
+                            (random::unit_f64(cell), random::unit_f64(cell ^ 1))
+                        } else {
+                            (0.5, 0.5)
+                        };
+                        samples.push(
+                            &(corner + &((u as f64 + ju) * uvec)) + &((v as f64 + jv) * vvec),
+                        );
+                    }
+                }
+                samples
+            }
         }
     }
 }
@@ -1,75 +1,130 @@
+use std::sync::Arc;
+
 use crate::{
     canvas::Color,
     vector::{Point, Vec4},
 };
 
-use super::light::Light;
+use super::{
+    light::{Light, LightKind},
+    pattern::Pattern,
+    Shape,
+};
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct Material {
     pub color: Color,
+    // when there is a pattern it gives the color instead of `color`
+    pub pattern: Option<Arc<dyn Pattern>>,
     pub ambient: f64,
     pub diffuse: f64,
     pub specular: f64,
     pub shininess: f64,
+    // 0 is not reflective at all, 1 is a perfect mirror
+    pub reflective: f64,
+    // 0 is opaque, 1 lets all the light through
+    pub transparency: f64,
+    // how much light bends entering the material, 1 for vacuum (and air), 1.33 for water, 1.52 for glass
+    pub refractive_index: f64,
 }
 
 impl Default for Material {
     fn default() -> Self {
         Material {
             color: Color::white(),
+            pattern: None,
             ambient: 0.1,
             diffuse: 0.9,
             specular: 0.9,
             shininess: 200.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
         }
     }
 }
 
+// patterns can't be compared, two materials have the same pattern only if they share it
+impl PartialEq for Material {
+    fn eq(&self, other: &Self) -> bool {
+        let same_pattern = match (&self.pattern, &other.pattern) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_pattern
+            && self.color == other.color
+            && self.ambient == other.ambient
+            && self.diffuse == other.diffuse
+            && self.specular == other.specular
+            && self.shininess == other.shininess
+            && self.reflective == other.reflective
+            && self.transparency == other.transparency
+            && self.refractive_index == other.refractive_index
+    }
+}
+
 impl Material {
+    // object is the shape being lit, the pattern (if any) is looked up on it,
+    // parents (the groups it's in, innermost first) and time (when it was hit) place the point in it's object space
+    // light_intensity is how much of the light reaches the point, 0 in full shadow and 1 when nothing is in the way
+    #[allow(clippy::too_many_arguments)]
     pub fn lighting(
-        &mut self,
+        &self,
+        object: &dyn Shape,
+        parents: &[&dyn Shape],
+        time: f64,
         light: &Light,
         point: &Point,
         eye_vector: &Vec4,
         normal_vector: &Vec4,
-        in_shadow: bool,
+        light_intensity: f64,
     ) -> Color {
-        let (diffuse, specular, ambient);
+        let color = match self.pattern.as_ref() {
+            Some(pattern) => pattern.pattern_at_shape(object, parents, time, point),
+            None => self.color.clone(),
+        };
         // combine the surface color with the light's color/intensity
-        let effective_color = self.color.as_ref() * light.intensity.as_ref();
-        // find the direction to the light source
-        let light_vector = (light.position.as_ref() - point).normalize();
-        // compute the ambient contribution
-        ambient = self.ambient * effective_color.as_ref();
-        if in_shadow {
+        let effective_color = color.as_ref() * light.intensity.as_ref();
+        // compute the ambient contribution, it gets dimmer with the distance to the light too
+        let ambient = match light.kind {
+            LightKind::Directional { .. } => self.ambient * effective_color.as_ref(),
+            _ => {
+                let attenuation = light.attenuation((&light.position - point).magnitude());
+                (self.ambient * attenuation) * effective_color.as_ref()
+            }
+        };
+        if light_intensity <= 0.0 {
             return ambient;
         }
-        //  light_dot_normal represents the cosine of the angle between the
-        //  light vector and the normal vector. A negative number means the
-        //  light is on the other side of the surface.
-        let light_dot_normal = light_vector.dot(normal_vector.as_ref());
-        if light_dot_normal < 0.0 {
-            // black
-            diffuse = Color::black();
-            // black
-            specular = Color::black();
-        } else {
+        // an area light is many lights, the point is lit by every sample on it and the results are averaged
+        let light_vectors = light.light_vectors(point);
+        let mut sum = Color::black();
+        for (light_vector, distance) in light_vectors.iter() {
+            //  light_dot_normal represents the cosine of the angle between the
+            //  light vector and the normal vector. A negative number means the
+            //  light is on the other side of the surface.
+            let light_dot_normal = light_vector.dot(normal_vector.as_ref());
+            // outside the cone of a spot light it's the same as being on the other side
+            let cone_factor = light.cone_factor(light_vector);
+            if light_dot_normal < 0.0 || cone_factor == 0.0 {
+                // black diffuse and specular
+                continue;
+            }
             // compute the diffuse contribution
-            diffuse = light_dot_normal * self.diffuse * &effective_color;
+            let mut lit = light_dot_normal * self.diffuse * &effective_color;
             //  reflect_dot_eye represents the cosine of the angle between the
             //  reflection vector and the eye vector. A negative number means the
             //  light reflects away from the eye.
-            let reflect_vector = (-light_vector).reflect(normal_vector.as_ref());
+            let reflect_vector = (-light_vector.clone()).reflect(normal_vector.as_ref());
             let reflect_dot_eye = reflect_vector.dot(eye_vector.as_ref());
-            if reflect_dot_eye <= 0.0 {
-                specular = Color::black();
-            } else {
+            if reflect_dot_eye > 0.0 {
                 //  compute the specular contribution
                 let factor = reflect_dot_eye.powf(self.shininess);
-                specular = self.specular * factor * light.intensity.as_ref();
+                lit = lit + self.specular * factor * light.intensity.as_ref();
             }
+            sum = sum + (cone_factor * light.attenuation(*distance)) * lit;
         }
-        ambient + diffuse + specular
+        ambient + (light_intensity / light_vectors.len() as f64) * sum
     }
 }
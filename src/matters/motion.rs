@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+
+use crate::{
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{bounds::BoundingBox, material::Material, Intersection, Shape};
+
+/// NOTES:
+/// 1. a motion is a list of keyframes, a transformation at some time. between two keyframes the transformation is blended from one to the other,
+///    before the first one and after the last one it stays put.
+/// 2. the matrices are blended number by number, it's exact for moving and scaling. a rotation shrinks a bit half way between two keyframes,
+///    a big rotation should be split into several keyframes.
+/// 3. `Moving` works like a group with one child, the child keeps it's own transformation and the motion moves it (and it's normals) around the world.
+///    it adds itself to the `parents` of the intersections of the child, the time of the intersection says where it was.
+/// 4. blending moves every point in a straight line between two keyframes, so the box around the child at every keyframe contains the child at any time.
+/// 5. the camera shoots every ray at some time while it's shutter is open, a shape moving during that time is smeared and the ones not moving stay sharp.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Motion {
+    // sorted by time, there is always at least one
+    keyframes: Vec<(f64, Matrix)>,
+}
+
+impl Motion {
+    // from start at time 0 to end at time 1
+    pub fn linear(start: Matrix, end: Matrix) -> Self {
+        Self::keyframed(vec![(0.0, start), (1.0, end)])
+    }
+
+    // keyframes are (time, transformation), in any order, no keyframes at all does not move
+    pub fn keyframed(mut keyframes: Vec<(f64, Matrix)>) -> Self {
+        if keyframes.is_empty() {
+            keyframes.push((0.0, Matrix::identity_4x4()));
+        }
+        keyframes.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Self { keyframes }
+    }
+
+    pub fn keyframes(&self) -> &[(f64, Matrix)] {
+        &self.keyframes
+    }
+
+    pub fn transformation_at(&self, time: f64) -> Matrix {
+        // the first keyframe after the time
+        let next = self.keyframes.partition_point(|(t, _)| *t <= time);
+        if next == 0 {
+            return self.keyframes[0].1.clone();
+        }
+        if next == self.keyframes.len() {
+            return self.keyframes[next - 1].1.clone();
+        }
+        let (t0, from) = &self.keyframes[next - 1];
+        let (t1, to) = &self.keyframes[next];
+        let blend = (time - t0) / (t1 - t0);
+        Matrix(
+            from.0
+                .iter()
+                .zip(to.0.iter())
+                .map(|(from_row, to_row)| {
+                    from_row
+                        .iter()
+                        .zip(to_row.iter())
+                        .map(|(a, b)| a + blend * (b - a))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Moving {
+    pub motion: Motion,
+    // not used for shading, the child has it's own material
+    pub material: Material,
+    child: Box<dyn Shape>,
+}
+
+impl Moving {
+    pub fn new(child: Box<dyn Shape>, motion: Motion) -> Self {
+        Self {
+            motion,
+            material: Material::default(),
+            child,
+        }
+    }
+
+    pub fn child(&self) -> &dyn Shape {
+        self.child.as_ref()
+    }
+}
+
+impl Shape for Moving {
+    // where it starts
+    fn transformation(&self) -> &Matrix {
+        &self.motion.keyframes[0].1
+    }
+
+    fn transformation_at(&self, time: f64) -> Cow<'_, Matrix> {
+        Cow::Owned(self.motion.transformation_at(time))
+    }
+
+    // stops moving
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.motion = Motion::keyframed(vec![(0.0, transformation_matrix)]);
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.child.parent_space_bounds()
+    }
+
+    // everywhere it goes
+    fn parent_space_bounds(&self) -> BoundingBox {
+        let bounds = self.bounds();
+        self.motion
+            .keyframes
+            .iter()
+            .fold(BoundingBox::empty(), |mut all, (_, transformation)| {
+                all.merge(&bounds.transform(transformation));
+                all
+            })
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut xs = self.child.intersect(ray);
+        for intersection in xs.iter_mut() {
+            intersection.parents.push(self);
+        }
+        xs
+    }
+
+    fn local_normal_at(&self, _point: &Point, _hit: Option<&Intersection>) -> Vec4 {
+        unreachable!("a moving shape has no surface, normals are always computed on it's child")
+    }
+}
@@ -0,0 +1,313 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{canvas::Color, matrix::Matrix, vector::Point};
+
+use super::{perlin, Shape};
+
+/// NOTES:
+/// 1. a pattern gives a color for every point on a surface, it is looked up in pattern space.
+/// 2. the point on the surface is brought into the object space of the shape first and then into pattern space with the inverse of the pattern transformation,
+///    so moving/scaling the shape moves/scales the pattern with it and the pattern can still be moved/scaled on the shape.
+/// 3. a pattern only has to implement `pattern_at` which works in pattern space, `pattern_at_shape` takes care of the conversion.
+/// 4. like shapes the trait is object safe, materials hold patterns as `Arc<dyn Pattern>` so they can be cloned and shared between threads.
+/// 5. the two colors of stripes, gradients, rings and checkers are patterns too (a solid color is just a `Solid` pattern),
+///    a nested pattern is looked up in the pattern space of it's parent and then brought into it's own pattern space with it's own transformation.
+/// 6. `Blend` averages two patterns and `Perturbed` moves the lookup point by 3d perlin noise before looking up another pattern,
+///    together with gradients and rings they give organic looking surfaces like marble and wood.
+pub trait Pattern: Debug + Send + Sync {
+    // transformation which takes the pattern from pattern space to object space
+    fn transformation(&self) -> &Matrix;
+    // set transformation to the pattern
+    fn set_transformation(&mut self, transformation_matrix: Matrix);
+    // color at a point which is already in pattern space
+    fn pattern_at(&self, point: &Point) -> Color;
+
+    // color at a point in the space the pattern is placed in (object space, or the pattern space of a parent pattern)
+    fn transformed_pattern_at(&self, point: &Point) -> Color {
+        let pattern_point = &self.transformation().inverse_4x4().unwrap() * point;
+        self.pattern_at(&pattern_point)
+    }
+    // color at a point (in world space) on the shape, parents are the groups the shape is in (innermost first) and time is when the point was hit
+    fn pattern_at_shape(
+        &self,
+        object: &dyn Shape,
+        parents: &[&dyn Shape],
+        time: f64,
+        point: &Point,
+    ) -> Color {
+        let object_point = object.world_to_object(point, parents, time);
+        self.transformed_pattern_at(&object_point)
+    }
+}
+
+// the same color everywhere, used as the colors of the other patterns
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solid {
+    pub color: Color,
+    pub transformation: Matrix,
+}
+
+impl Solid {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+}
+
+impl Pattern for Solid {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, _point: &Point) -> Color {
+        self.color.clone()
+    }
+}
+
+// a and b wrapped as solid patterns
+fn solids(a: Color, b: Color) -> (Arc<dyn Pattern>, Arc<dyn Pattern>) {
+    (Arc::new(Solid::new(a)), Arc::new(Solid::new(b)))
+}
+
+// alternating stripes of a and b along x, each 1 unit wide
+#[derive(Debug, Clone)]
+pub struct Stripe {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: Matrix,
+}
+
+impl Stripe {
+    pub fn new(a: Color, b: Color) -> Self {
+        let (a, b) = solids(a, b);
+        Self::with_patterns(a, b)
+    }
+
+    pub fn with_patterns(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self {
+            a,
+            b,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+}
+
+impl Pattern for Stripe {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        if point.0.floor().rem_euclid(2.0) == 0.0 {
+            self.a.transformed_pattern_at(point)
+        } else {
+            self.b.transformed_pattern_at(point)
+        }
+    }
+}
+
+// blends linearly from a to b along x, from 0 to 1 (and again from every whole number to the next)
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: Matrix,
+}
+
+impl Gradient {
+    pub fn new(a: Color, b: Color) -> Self {
+        let (a, b) = solids(a, b);
+        Self::with_patterns(a, b)
+    }
+
+    pub fn with_patterns(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self {
+            a,
+            b,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+}
+
+impl Pattern for Gradient {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        let (a, b) = (
+            self.a.transformed_pattern_at(point),
+            self.b.transformed_pattern_at(point),
+        );
+        let fraction = point.0 - point.0.floor();
+        &a + &(fraction * &(&b - &a))
+    }
+}
+
+// concentric rings of a and b around the y axis, each 1 unit wide
+#[derive(Debug, Clone)]
+pub struct Ring {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: Matrix,
+}
+
+impl Ring {
+    pub fn new(a: Color, b: Color) -> Self {
+        let (a, b) = solids(a, b);
+        Self::with_patterns(a, b)
+    }
+
+    pub fn with_patterns(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self {
+            a,
+            b,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+}
+
+impl Pattern for Ring {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        if (point.0.powi(2) + point.2.powi(2)).sqrt().floor() % 2.0 == 0.0 {
+            self.a.transformed_pattern_at(point)
+        } else {
+            self.b.transformed_pattern_at(point)
+        }
+    }
+}
+
+// 3d checkers, cubes of a and b each 1 unit wide, a plane through them looks like a checker board
+#[derive(Debug, Clone)]
+pub struct Checkers {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: Matrix,
+}
+
+impl Checkers {
+    pub fn new(a: Color, b: Color) -> Self {
+        let (a, b) = solids(a, b);
+        Self::with_patterns(a, b)
+    }
+
+    pub fn with_patterns(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self {
+            a,
+            b,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+}
+
+impl Pattern for Checkers {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        // rem_euclid so negative coordinates alternate too
+        if (point.0.floor() + point.1.floor() + point.2.floor()).rem_euclid(2.0) == 0.0 {
+            self.a.transformed_pattern_at(point)
+        } else {
+            self.b.transformed_pattern_at(point)
+        }
+    }
+}
+
+// average of a and b at every point, like two stripe patterns at right angles for a plaid
+#[derive(Debug, Clone)]
+pub struct Blend {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: Matrix,
+}
+
+impl Blend {
+    pub fn with_patterns(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> Self {
+        Self {
+            a,
+            b,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+}
+
+impl Pattern for Blend {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        0.5 * (self.a.transformed_pattern_at(point) + self.b.transformed_pattern_at(point))
+    }
+}
+
+// another pattern looked up at a point moved by perlin noise, scale is how far the point can move
+#[derive(Debug, Clone)]
+pub struct Perturbed {
+    pub pattern: Arc<dyn Pattern>,
+    pub scale: f64,
+    pub transformation: Matrix,
+}
+
+impl Perturbed {
+    pub fn new(pattern: Arc<dyn Pattern>, scale: f64) -> Self {
+        Self {
+            pattern,
+            scale,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+}
+
+impl Pattern for Perturbed {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        // the noise at the same point would move every axis by the same amount, offset the lookup for y and z
+        let (x, y, z) = (point.0, point.1, point.2);
+        let perturbed = Point::new(
+            x + perlin::noise(x, y, z) * self.scale,
+            y + perlin::noise(x, y, z + 1.0) * self.scale,
+            z + perlin::noise(x, y, z + 2.0) * self.scale,
+        );
+        self.pattern.transformed_pattern_at(&perturbed)
+    }
+}
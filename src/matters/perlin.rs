@@ -0,0 +1,96 @@
+// the numbers 0 to 255 shuffled, from the reference implementation
+const PERMUTATION: [usize; 256] = [
+    151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225, 140, 36, 103, 30, 69,
+    142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148, 247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219,
+    203, 117, 35, 11, 32, 57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+    74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122, 60, 211, 133, 230,
+    220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54, 65, 25, 63, 161, 1, 216, 80, 73, 209, 76,
+    132, 187, 208, 89, 18, 169, 200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173,
+    186, 3, 64, 52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212, 207, 206,
+    59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213, 119, 248, 152, 2, 44, 154, 163,
+    70, 221, 153, 101, 155, 167, 43, 172, 9, 129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232,
+    178, 185, 112, 104, 218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162,
+    241, 81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157, 184, 84, 204,
+    176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93, 222, 114, 67, 29, 24, 72, 243, 141,
+    128, 195, 78, 66, 215, 61, 156, 180,
+];
+
+fn permutation(index: usize) -> usize {
+    PERMUTATION[index % 256]
+}
+
+// 6t^5 - 15t^4 + 10t^3, it has zero first and second derivatives at 0 and 1 so the cubes join smoothly
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+// dot product of (x, y, z) with one of 12 gradients (the directions to the middle of the edges of a cube) picked by the hash
+fn grad(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+/// NOTES:
+/// 1. ken perlin's improved noise, a smooth pseudo random value in about -1 to 1 for every point in space.
+/// 2. space is split in unit cubes, every corner of a cube gets a gradient picked with the permutation table,
+///    the noise is the dot products of the gradients with the point (relative to the corners) blended across the cube with a smooth curve.
+/// 3. the noise is always 0 at whole numbers, and the same point always gives the same value so renders don't change between runs.
+pub fn noise(x: f64, y: f64, z: f64) -> f64 {
+    // unit cube containing the point, wrapped to the table size (rem_euclid keeps negative coordinates working)
+    let (xi, yi, zi) = (
+        x.floor().rem_euclid(256.0) as usize,
+        y.floor().rem_euclid(256.0) as usize,
+        z.floor().rem_euclid(256.0) as usize,
+    );
+    // point relative to the cube
+    let (x, y, z) = (x - x.floor(), y - y.floor(), z - z.floor());
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+
+    // hash of the 8 cube corners
+    let a = permutation(xi) + yi;
+    let (aa, ab) = (permutation(a) + zi, permutation(a + 1) + zi);
+    let b = permutation(xi + 1) + yi;
+    let (ba, bb) = (permutation(b) + zi, permutation(b + 1) + zi);
+
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(
+                u,
+                grad(permutation(aa), x, y, z),
+                grad(permutation(ba), x - 1.0, y, z),
+            ),
+            lerp(
+                u,
+                grad(permutation(ab), x, y - 1.0, z),
+                grad(permutation(bb), x - 1.0, y - 1.0, z),
+            ),
+        ),
+        lerp(
+            v,
+            lerp(
+                u,
+                grad(permutation(aa + 1), x, y, z - 1.0),
+                grad(permutation(ba + 1), x - 1.0, y, z - 1.0),
+            ),
+            lerp(
+                u,
+                grad(permutation(ab + 1), x, y - 1.0, z - 1.0),
+                grad(permutation(bb + 1), x - 1.0, y - 1.0, z - 1.0),
+            ),
+        ),
+    )
+}
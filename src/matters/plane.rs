@@ -0,0 +1,63 @@
+use crate::{
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{bounds::BoundingBox, material::Material, Intersection, Shape, EPSILON};
+
+/// NOTES:
+/// 1. in object space the plane is the xz plane (y = 0), extending infinitely in x and z.
+/// 2. the normal is the same at every point of the plane i.e (0, 1, 0) in object space.
+/// 3. a ray parallel to the plane (direction.y = 0) never intersects it, a ray lying inside the plane is treated the same way since the plane is infinitely thin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plane {
+    // identity matrix as default transformation, it can be changed so making it public
+    pub transformation: Matrix,
+    pub material: Material,
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Self {
+            transformation: Matrix::identity_4x4(),
+            material: Material::default(),
+        }
+    }
+}
+
+impl Shape for Plane {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        if ray.direction.1.abs() < EPSILON {
+            return vec![];
+        }
+        // distance at which the ray reaches y = 0
+        let distance = -ray.origin.1 / ray.direction.1;
+        vec![Intersection::new(distance, self)]
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        // infinite in x and z but flat in y
+        BoundingBox::new(
+            Point::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+
+    fn local_normal_at(&self, _point: &Point, _hit: Option<&Intersection>) -> Vec4 {
+        // object space normal is constant
+        Vec4::new(0.0, 1.0, 0.0)
+    }
+}
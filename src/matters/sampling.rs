@@ -0,0 +1,255 @@
+use crate::{canvas::Color, random};
+
+/// NOTES:
+/// 1. a pixel is a small square of the image, a ray through it's center only sees one point of it, so the edges of objects are jagged (aliasing).
+///    shooting more rays through every pixel and averaging their colors gives the pixel a mix of everything inside it.
+/// 2. the pattern decides where the rays go:
+///    - `Grid` puts them on the centers of the cells of a n x n grid, it's regular so it can still show patterns (moire) on fine detail.
+///    - `Stratified` puts one at a random place in every cell of the grid, the noise looks better than the moire and they are still spread evenly.
+///    - `Jittered` puts them anywhere in the pixel, they can clump together.
+/// 3. the filter decides how much every ray counts, based on how far it is from the pixel's center.
+///    filters wider than the pixel (`radius` more than half a pixel) also take rays around the pixel, it gives a smoother image.
+///    - `Box` counts every ray the same.
+///    - `Tent` counts less the further the ray is, down to 0 one pixel away.
+///    - `Gaussian` is like the tent but smoother.
+///    - `Mitchell` is sharper than the others, rays further away count a little negatively, which keeps edges crisp.
+/// 4. offsets are random but seeded with the pixel, the same image is rendered every time.
+/// 5. adaptive sampling shoots `samples` rays through every pixel first, then adds rays (up to `max_samples`) only to the pixels which need them:
+///    the ones whose rays don't agree (their brightness varies more than `threshold`), and the ones whose color differs by more than `threshold` from a neighbour.
+///    with a single first sample there is nothing to vary, only the contrast with the neighbours finds the edges.
+///    the rays are added a few at a time and the pixel stops getting more as soon as they agree, so a slightly noisy pixel doesn't pay for `max_samples`.
+/// 6. a real lens lets light in through the whole aperture, not just a pinhole, only points at the focal distance end up sharp.
+///    the shape of the aperture is the shape out of focus highlights (bokeh) take, a disk or a polygon like the blades of a camera's diaphragm.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SamplePattern {
+    #[default]
+    Grid,
+    Stratified,
+    Jittered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Filter {
+    #[default]
+    Box,
+    Tent,
+    Gaussian,
+    Mitchell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ApertureShape {
+    #[default]
+    Disk,
+    // a regular polygon, rotation (in radians) turns it around it's center
+    Polygon {
+        sides: usize,
+        rotation: f64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveSampling {
+    // rays a pixel can get at most, counting the first ones
+    pub max_samples: usize,
+    // how much the colors can differ before more rays are needed, 0.05 is hard to see
+    pub threshold: f64,
+}
+
+impl Default for AdaptiveSampling {
+    fn default() -> Self {
+        Self {
+            max_samples: 64,
+            threshold: 0.05,
+        }
+    }
+}
+
+// the rays shot through a pixel so far, more can be added later
+#[derive(Debug, Clone)]
+pub struct PixelSamples {
+    weighted_sum: Color,
+    total_weight: f64,
+    sum: Color,
+    // brightness of the samples, for the variance
+    brightness_sum: f64,
+    brightness_squares: f64,
+    count: usize,
+}
+
+impl Default for PixelSamples {
+    fn default() -> Self {
+        Self {
+            weighted_sum: Color::black(),
+            total_weight: 0.0,
+            sum: Color::black(),
+            brightness_sum: 0.0,
+            brightness_squares: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl PixelSamples {
+    pub fn add(&mut self, color: &Color, weight: f64) {
+        let (r, g, b) = color.rgb();
+        let brightness = (r + g + b) / 3.0;
+        self.weighted_sum = &self.weighted_sum + &(weight * color);
+        self.total_weight += weight;
+        self.sum = &self.sum + color;
+        self.brightness_sum += brightness;
+        self.brightness_squares += brightness * brightness;
+        self.count += 1;
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // the filtered color of the pixel
+    pub fn color(&self) -> Color {
+        if self.total_weight > 0.0 {
+            (1.0 / self.total_weight) * &self.weighted_sum
+        } else if self.count > 0 {
+            // the negative parts of a mitchell filter can cancel out the rest with very few samples
+            (1.0 / self.count as f64) * &self.sum
+        } else {
+            Color::black()
+        }
+    }
+
+    // variance of the brightness of the samples, 0 when they all agree
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        let n = self.count as f64;
+        let mean = self.brightness_sum / n;
+        (self.brightness_squares / n - mean * mean).max(0.0)
+    }
+}
+
+// how quickly the gaussian goes down
+const GAUSSIAN_ALPHA: f64 = 2.0;
+// B and C of the Mitchell-Netravali filter, 1/3 is what they recommend
+const MITCHELL_B: f64 = 1.0 / 3.0;
+const MITCHELL_C: f64 = 1.0 / 3.0;
+
+impl Filter {
+    // how far from the pixel's center (in pixels) a ray can be and still count
+    pub fn radius(&self) -> f64 {
+        match self {
+            Filter::Box => 0.5,
+            Filter::Tent => 1.0,
+            Filter::Gaussian => 1.5,
+            Filter::Mitchell => 2.0,
+        }
+    }
+
+    // how much a ray (dx, dy) pixels away from the pixel's center counts
+    pub fn weight(&self, dx: f64, dy: f64) -> f64 {
+        // every filter is the same along x and along y
+        self.weight_1d(dx) * self.weight_1d(dy)
+    }
+
+    fn weight_1d(&self, d: f64) -> f64 {
+        let d = d.abs();
+        let radius = self.radius();
+        if d > radius {
+            return 0.0;
+        }
+        match self {
+            Filter::Box => 1.0,
+            Filter::Tent => radius - d,
+            // shifted down so it goes to 0 at the radius instead of stopping suddenly
+            Filter::Gaussian => {
+                (-GAUSSIAN_ALPHA * d * d).exp() - (-GAUSSIAN_ALPHA * radius * radius).exp()
+            }
+            Filter::Mitchell => {
+                let (b, c) = (MITCHELL_B, MITCHELL_C);
+                // the filter is defined from 0 to 2
+                let x = 2.0 * d / radius;
+                if x < 1.0 {
+                    ((12.0 - 9.0 * b - 6.0 * c) * x.powi(3)
+                        + (-18.0 + 12.0 * b + 6.0 * c) * x.powi(2)
+                        + (6.0 - 2.0 * b))
+                        / 6.0
+                } else {
+                    ((-b - 6.0 * c) * x.powi(3)
+                        + (6.0 * b + 30.0 * c) * x.powi(2)
+                        + (-12.0 * b - 48.0 * c) * x
+                        + (8.0 * b + 24.0 * c))
+                        / 6.0
+                }
+            }
+        }
+    }
+}
+
+impl ApertureShape {
+    // a point on the aperture (of radius 1) from two random numbers from 0 to 1, the points are spread evenly over it's area
+    pub fn sample(&self, u: f64, v: f64) -> (f64, f64) {
+        match *self {
+            ApertureShape::Disk => {
+                // the square root keeps the points from bunching up in the middle
+                let (r, theta) = (u.sqrt(), 2.0 * std::f64::consts::PI * v);
+                (r * theta.cos(), r * theta.sin())
+            }
+            ApertureShape::Polygon { sides, rotation } => {
+                let sides = sides.max(3);
+                // v picks one of the triangles between the center and an edge, what is left of it picks where along the edge
+                let scaled = v * sides as f64;
+                let triangle = (scaled.floor() as usize).min(sides - 1);
+                let along = scaled - triangle as f64;
+                let corner = |i: usize| {
+                    let angle = rotation + 2.0 * std::f64::consts::PI * i as f64 / sides as f64;
+                    (angle.cos(), angle.sin())
+                };
+                let (a, b) = (corner(triangle), corner(triangle + 1));
+                let edge = (a.0 + along * (b.0 - a.0), a.1 + along * (b.1 - a.1));
+                let r = u.sqrt();
+                (r * edge.0, r * edge.1)
+            }
+        }
+    }
+}
+
+impl SamplePattern {
+    // where the rays go, as offsets (in pixels) from the pixel's center, all within `radius` of it
+    // grid and stratified use the biggest n x n grid which fits in `count`, so there are at least 1 and at most `count` offsets
+    pub fn offsets(&self, count: usize, radius: f64, seed: u64) -> Vec<(f64, f64)> {
+        let count = count.max(1);
+        let side = 2.0 * radius;
+        match self {
+            SamplePattern::Jittered => (0..count)
+                .map(|i| {
+                    let sample = random::seed(&[seed, i as u64]);
+                    (
+                        (random::unit_f64(sample) - 0.5) * side,
+                        (random::unit_f64(sample ^ 1) - 0.5) * side,
+                    )
+                })
+                .collect(),
+            SamplePattern::Grid | SamplePattern::Stratified => {
+                let n = (count as f64).sqrt().floor() as usize;
+                let cell = side / n as f64;
+                let mut offsets = Vec::with_capacity(n * n);
+                for v in 0..n {
+                    for u in 0..n {
+                        let (ju, jv) = if *self == SamplePattern::Stratified {
+                            let sample = random::seed(&[seed, u as u64, v as u64]);
+                            (random::unit_f64(sample), random::unit_f64(sample ^ 1))
+                        } else {
+                            (0.5, 0.5)
+                        };
+                        offsets.push((
+                            (u as f64 + ju) * cell - radius,
+                            (v as f64 + jv) * cell - radius,
+                        ));
+                    }
+                }
+                offsets
+            }
+        }
+    }
+}
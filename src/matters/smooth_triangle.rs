@@ -0,0 +1,86 @@
+use crate::{
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{
+    bounds::BoundingBox, material::Material, triangle::intersect_triangle, Intersection, Shape,
+};
+
+/// NOTES:
+/// 1. same as a triangle but every corner has it's own normal (n1, n2, n3), like the vertex normals of a mesh.
+/// 2. the normal at a point is interpolated from the corner normals using the u/v of the hit,
+///    which makes a mesh of smooth triangles look curved instead of faceted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmoothTriangle {
+    pub p1: Point,
+    pub p2: Point,
+    pub p3: Point,
+    pub n1: Vec4,
+    pub n2: Vec4,
+    pub n3: Vec4,
+    // edge from p1 to p2
+    pub e1: Vec4,
+    // edge from p1 to p3
+    pub e2: Vec4,
+    // identity matrix as default transformation, it can be changed so making it public
+    pub transformation: Matrix,
+    pub material: Material,
+}
+
+impl SmoothTriangle {
+    pub fn new(p1: Point, p2: Point, p3: Point, n1: Vec4, n2: Vec4, n3: Vec4) -> Self {
+        let e1 = &p2 - &p1;
+        let e2 = &p3 - &p1;
+        Self {
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1,
+            e2,
+            transformation: Matrix::identity_4x4(),
+            material: Material::default(),
+        }
+    }
+}
+
+impl Shape for SmoothTriangle {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        match intersect_triangle(&self.p1, &self.e1, &self.e2, ray) {
+            Some((t, u, v)) => vec![Intersection::with_uv(t, self, u, v)],
+            None => vec![],
+        }
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        bounds.add_point(&self.p1);
+        bounds.add_point(&self.p2);
+        bounds.add_point(&self.p3);
+        bounds
+    }
+
+    fn local_normal_at(&self, _point: &Point, hit: Option<&Intersection>) -> Vec4 {
+        match hit.and_then(|hit| hit.uv) {
+            Some((u, v)) => u * &self.n2 + v * &self.n3 + (1.0 - u - v) * &self.n1,
+            // without a hit there is nothing to interpolate with, fall back to the flat normal
+            None => self.e2.cross(&self.e1).normalize(),
+        }
+    }
+}
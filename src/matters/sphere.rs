@@ -7,9 +7,7 @@ use crate::{
     vector::{Point, Vec4},
 };
 
-use super::{
-    light::Light, material::Material, Intersectable, Intersection, PrerareComputation, Shape,
-};
+use super::{bounds::BoundingBox, light::Light, material::Material, Intersection, Shape};
 
 /// NOTES:
 /// 1. to bring some point/vector from world space to object space multiply the inverse of transformation matrix of object(sphere) with the point/vector i.e transformation.inverse() * point/vector
@@ -30,6 +28,14 @@ pub struct Sphere {
 }
 
 impl Sphere {
+    // fully transparent sphere with the refractive index of glass
+    pub fn glass() -> Self {
+        let mut sphere = Self::default();
+        sphere.material.transparency = 1.0;
+        sphere.material.refractive_index = 1.5;
+        sphere
+    }
+
     pub fn simple_sphere_to_canvas(&self) {
         let mut canvas = Canvas::new(300, 300);
         let red = Color::new(1.0, 0.0, 0.0);
@@ -56,7 +62,7 @@ impl Sphere {
                 if intersections.is_empty() {
                     canvas.write_pixel((x as usize, y as usize), &black);
                 } else {
-                    if Sphere::hits(&intersections).is_some() {
+                    if Intersection::hits(&intersections).is_some() {
                         canvas.write_pixel((x as usize, y as usize), &red);
                     }
                 }
@@ -103,16 +109,19 @@ impl Sphere {
                 if intersections.is_empty() {
                     canvas.write_pixel((x as usize, y as usize), &black);
                 } else {
-                    if let Some(mut hit) = Sphere::hits(&intersections) {
+                    if let Some(hit) = Intersection::hits(&intersections) {
                         let point = ray.position(hit.distance);
-                        let normal_vector = hit.object.normal_at(&point);
+                        let normal_vector = hit.object.normal_at(&point, Some(&hit));
                         let eye_vector = -ray.direction;
-                        let lighting = hit.object.material.lighting(
+                        let lighting = hit.object.material().lighting(
+                            hit.object,
+                            &hit.parents,
+                            hit.time,
                             &light,
                             &point,
                             &eye_vector,
                             &normal_vector,
-                            false,
+                            1.0,
                         );
                         canvas.write_pixel((x as usize, y as usize), &lighting);
                     }
@@ -144,65 +153,24 @@ pub struct Wall {
     height: f64,
 }
 
-impl Intersectable for Sphere {
-    fn hits(intersections: &[Intersection<Sphere>]) -> Option<Intersection<Sphere>> {
-        // if distance is negative than the object is behind the ray so exclude those intersections in hits
-        let mut positive_intersections = intersections
-            .iter()
-            .filter(|&i| i.distance >= 0.0)
-            .collect::<Vec<&Intersection<Sphere>>>();
-        if positive_intersections.is_empty() {
-            // if objects are not in front of ray
-            None
-        } else {
-            positive_intersections.sort();
-            Some(positive_intersections[0].clone())
-        }
+impl Shape for Sphere {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
     }
-
-    fn prepare_computation(
-        intersection: &Intersection<Sphere>,
-        ray: &Ray,
-    ) -> PrerareComputation<Sphere> {
-        let point = ray.position(intersection.distance);
-        let normalv = Sphere::normal_at(&intersection.object, &point);
-        let eyev = -ray.direction.clone();
-        let (normalv, inside) = if normalv.dot(&eyev) < 0.0 {
-            (-normalv, true)
-        } else {
-            (normalv, false)
-        };
-        let over_point = &point + &(0.000000001 * &normalv);
-        PrerareComputation {
-            distance: intersection.distance,
-            normalv,
-            object: intersection.object.clone(),
-            point,
-            eyev,
-            inside,
-            over_point,
-        }
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
     }
-}
-
-impl Shape for Sphere {
-    fn intersect(&self, ray: &Ray) -> Vec<Intersection<Sphere>> {
-        //transform the ray from world space coordinate to object space coordinate by appling inverse of sphere transformation to the ray
-        let transformed_ray = ray.transform(self.transformation.inverse_4x4().unwrap());
+    fn material(&self) -> &Material {
+        &self.material
+    }
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
         //because there is gona be 2 point at most
         let mut intersections = Vec::with_capacity(2);
         // vector from sphere center to ray
-        let sphere_to_ray = transformed_ray.origin.as_ref() - self.origin.as_ref();
+        let sphere_to_ray = ray.origin.as_ref() - self.origin.as_ref();
         // finding discriminant
-        let a = transformed_ray
-            .direction
-            .as_ref()
-            .dot(transformed_ray.direction.as_ref());
-        let b = 2.0
-            * transformed_ray
-                .direction
-                .as_ref()
-                .dot(sphere_to_ray.as_ref());
+        let a = ray.direction.as_ref().dot(ray.direction.as_ref());
+        let b = 2.0 * ray.direction.as_ref().dot(sphere_to_ray.as_ref());
         let c = sphere_to_ray.as_ref().dot(sphere_to_ray.as_ref()) - 1.0;
         let discriminant = b * b - 4.0 * a * c;
         // if discriminant < 0 the not intersection
@@ -212,33 +180,22 @@ impl Shape for Sphere {
             // send the same point twice even if one intraction
             intersections.push(Intersection::new(
                 (-b - discriminant.sqrt()) / (2.0 * a),
-                self.clone(),
+                self,
             ));
             intersections.push(Intersection::new(
                 (-b + discriminant.sqrt()) / (2.0 * a),
-                self.clone(),
+                self,
             ));
         }
         intersections
     }
-    fn normal_at(&self, point: &Point) -> Vec4 {
-        let world_point = point;
-        let inverse_transformation = self.transformation.inverse_4x4().unwrap();
-        // bring the world point (which we have to find normal to) into object space before calculating the normal
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+    fn local_normal_at(&self, point: &Point, _hit: Option<&Intersection>) -> Vec4 {
         // since normal is the vector from center of sphere and point of it's surface
         // the center of sphere (origin) is in object space
-        let object_point = inverse_transformation.as_ref() * world_point;
-        // normal in object space
-        let object_normal = object_point.as_ref() - &self.origin;
-        // convert the normal in object space to world space
-        // normally we only have to multiply sphere transfomation matrix to bring object space normal to world space
-        // not in this case
-        let mut world_normal = inverse_transformation.transpose() * object_normal;
         // since the sphere will always be unit sphere we explicitly don't have to normalize it
-        world_normal.3 = 0.0;
-        world_normal.normalize()
-    }
-    fn set_transformation(&mut self, transformation_matrix: Matrix) {
-        self.transformation = transformation_matrix;
+        point - &self.origin
     }
 }
@@ -0,0 +1,269 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    canvas::{Canvas, Color},
+    matrix::Matrix,
+    vector::Point,
+};
+
+use super::pattern::Pattern;
+
+/// NOTES:
+/// 1. a texture is a 2d pattern, it gives a color for a (u, v) coordinate where both go from 0 to 1 (v = 0 is the bottom of an image).
+/// 2. a uv mapping unwraps the surface of a 3d shape into that square, it turns a point in pattern space into (u, v).
+///    - spherical: u goes around the y axis and v from the south pole to the north pole (for the unit sphere).
+///    - planar: the xz plane is tiled with the texture, every unit square gets the whole texture.
+///    - cylindrical: u goes around the y axis and every unit of height repeats the texture (for the unit cylinder).
+///    - cube: every face of the unit cube gets it's own texture, see `CubeMap`.
+/// 3. `TextureMap` and `CubeMap` are patterns, so they have their own transformation and can be nested in other patterns.
+pub trait UvPattern: Debug + Send + Sync {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UvMapping {
+    Spherical,
+    Planar,
+    Cylindrical,
+}
+
+impl UvMapping {
+    pub fn map(&self, point: &Point) -> (f64, f64) {
+        match self {
+            UvMapping::Spherical => spherical_map(point),
+            UvMapping::Planar => planar_map(point),
+            UvMapping::Cylindrical => cylindrical_map(point),
+        }
+    }
+}
+
+fn spherical_map(point: &Point) -> (f64, f64) {
+    // azimuthal angle, from -pi to pi around the y axis
+    let theta = point.0.atan2(point.2);
+    let radius = (point.0.powi(2) + point.1.powi(2) + point.2.powi(2)).sqrt();
+    // polar angle, from 0 at the north pole to pi at the south pole
+    let phi = (point.1 / radius).acos();
+    let raw_u = theta / (2.0 * std::f64::consts::PI);
+    // raw_u goes from -0.5 to 0.5, flip it so u increases counter clockwise when seen from above
+    let u = 1.0 - (raw_u + 0.5);
+    let v = 1.0 - phi / std::f64::consts::PI;
+    (u, v)
+}
+
+fn planar_map(point: &Point) -> (f64, f64) {
+    (point.0.rem_euclid(1.0), point.2.rem_euclid(1.0))
+}
+
+fn cylindrical_map(point: &Point) -> (f64, f64) {
+    let theta = point.0.atan2(point.2);
+    let raw_u = theta / (2.0 * std::f64::consts::PI);
+    let u = 1.0 - (raw_u + 0.5);
+    (u, point.1.rem_euclid(1.0))
+}
+
+// checkers on the uv square, width by height of them
+#[derive(Debug, Clone, PartialEq)]
+pub struct UvCheckers {
+    pub width: f64,
+    pub height: f64,
+    pub a: Color,
+    pub b: Color,
+}
+
+impl UvCheckers {
+    pub fn new(width: f64, height: f64, a: Color, b: Color) -> Self {
+        Self {
+            width,
+            height,
+            a,
+            b,
+        }
+    }
+}
+
+impl UvPattern for UvCheckers {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        let u2 = (u * self.width).floor();
+        let v2 = (v * self.height).floor();
+        if (u2 + v2).rem_euclid(2.0) == 0.0 {
+            self.a.clone()
+        } else {
+            self.b.clone()
+        }
+    }
+}
+
+// an image (like one read with `Canvas::from_ppm_file`) stretched over the uv square
+pub struct UvImage {
+    pub canvas: Canvas,
+}
+
+impl UvImage {
+    pub fn new(canvas: Canvas) -> Self {
+        Self { canvas }
+    }
+}
+
+// a canvas can be huge, only print it's size
+impl Debug for UvImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UvImage({}x{})", self.canvas.width, self.canvas.height)
+    }
+}
+
+impl UvPattern for UvImage {
+    // bilinear filtering, the color is blended from the four pixels around the point so the image doesn't look blocky up close
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        let (width, height) = (self.canvas.width as usize, self.canvas.height as usize);
+        // v is flipped, 0 is the bottom of the image but the last row of the canvas
+        let x = u.clamp(0.0, 1.0) * (width - 1) as f64;
+        let y = (1.0 - v.clamp(0.0, 1.0)) * (height - 1) as f64;
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+        let top =
+            &((1.0 - fx) * self.canvas.pixel_at((x0, y0))) + &(fx * self.canvas.pixel_at((x1, y0)));
+        let bottom =
+            &((1.0 - fx) * self.canvas.pixel_at((x0, y1))) + &(fx * self.canvas.pixel_at((x1, y1)));
+        (1.0 - fy) * top + fy * bottom
+    }
+}
+
+// a uv pattern wrapped around a shape with a uv mapping
+#[derive(Debug, Clone)]
+pub struct TextureMap {
+    pub uv_pattern: Arc<dyn UvPattern>,
+    pub mapping: UvMapping,
+    pub transformation: Matrix,
+}
+
+impl TextureMap {
+    pub fn new(uv_pattern: Arc<dyn UvPattern>, mapping: UvMapping) -> Self {
+        Self {
+            uv_pattern,
+            mapping,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+}
+
+impl Pattern for TextureMap {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        let (u, v) = self.mapping.map(point);
+        self.uv_pattern.uv_pattern_at(u, v)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubeFace {
+    Left,
+    Right,
+    Front,
+    Back,
+    Up,
+    Down,
+}
+
+impl CubeFace {
+    // the face of the unit cube a point is on (or closest to), given by the coordinate with the largest absolute value
+    pub fn from_point(point: &Point) -> Self {
+        let (x, y, z) = (point.0, point.1, point.2);
+        let coord = x.abs().max(y.abs()).max(z.abs());
+        if coord == x {
+            CubeFace::Right
+        } else if coord == -x {
+            CubeFace::Left
+        } else if coord == y {
+            CubeFace::Up
+        } else if coord == -y {
+            CubeFace::Down
+        } else if coord == z {
+            CubeFace::Front
+        } else {
+            CubeFace::Back
+        }
+    }
+
+    // (u, v) on the face, as seen from outside the cube with up pointing to +y (or -z for the top and +z for the bottom)
+    pub fn uv(&self, point: &Point) -> (f64, f64) {
+        let (x, y, z) = (point.0, point.1, point.2);
+        let (u, v) = match self {
+            CubeFace::Front => (x + 1.0, y + 1.0),
+            CubeFace::Back => (1.0 - x, y + 1.0),
+            CubeFace::Left => (z + 1.0, y + 1.0),
+            CubeFace::Right => (1.0 - z, y + 1.0),
+            CubeFace::Up => (x + 1.0, 1.0 - z),
+            CubeFace::Down => (x + 1.0, z + 1.0),
+        };
+        // from -1..1 to 0..1
+        ((u / 2.0).rem_euclid(1.0), (v / 2.0).rem_euclid(1.0))
+    }
+}
+
+// cube mapping, a uv pattern for every face of the unit cube
+#[derive(Debug, Clone)]
+pub struct CubeMap {
+    pub left: Arc<dyn UvPattern>,
+    pub front: Arc<dyn UvPattern>,
+    pub right: Arc<dyn UvPattern>,
+    pub back: Arc<dyn UvPattern>,
+    pub up: Arc<dyn UvPattern>,
+    pub down: Arc<dyn UvPattern>,
+    pub transformation: Matrix,
+}
+
+impl CubeMap {
+    pub fn new(
+        left: Arc<dyn UvPattern>,
+        front: Arc<dyn UvPattern>,
+        right: Arc<dyn UvPattern>,
+        back: Arc<dyn UvPattern>,
+        up: Arc<dyn UvPattern>,
+        down: Arc<dyn UvPattern>,
+    ) -> Self {
+        Self {
+            left,
+            front,
+            right,
+            back,
+            up,
+            down,
+            transformation: Matrix::identity_4x4(),
+        }
+    }
+
+    pub fn face(&self, face: CubeFace) -> &dyn UvPattern {
+        match face {
+            CubeFace::Left => self.left.as_ref(),
+            CubeFace::Front => self.front.as_ref(),
+            CubeFace::Right => self.right.as_ref(),
+            CubeFace::Back => self.back.as_ref(),
+            CubeFace::Up => self.up.as_ref(),
+            CubeFace::Down => self.down.as_ref(),
+        }
+    }
+}
+
+impl Pattern for CubeMap {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        let face = CubeFace::from_point(point);
+        let (u, v) = face.uv(point);
+        self.face(face).uv_pattern_at(u, v)
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    matrix::Matrix,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+use super::{bounds::BoundingBox, material::Material, Intersection, Shape, EPSILON};
+
+/// NOTES:
+/// 1. a triangle is defined by it's three corners (p1, p2, p3) in object space,
+///    the edges and the normal are precomputed since they never change.
+/// 2. intersections use the Möller–Trumbore algorithm which also gives the barycentric u/v of the hit,
+///    u is the weight of p2 and v of p3 (so p1 gets 1 - u - v).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triangle {
+    pub p1: Point,
+    pub p2: Point,
+    pub p3: Point,
+    // edge from p1 to p2
+    pub e1: Vec4,
+    // edge from p1 to p3
+    pub e2: Vec4,
+    pub normal: Vec4,
+    // identity matrix as default transformation, it can be changed so making it public
+    pub transformation: Matrix,
+    pub material: Material,
+}
+
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point) -> Self {
+        let e1 = &p2 - &p1;
+        let e2 = &p3 - &p1;
+        let normal = e2.cross(&e1).normalize();
+        Self {
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal,
+            transformation: Matrix::identity_4x4(),
+            material: Material::default(),
+        }
+    }
+}
+
+// distance and barycentric (u, v) at which the ray hits the triangle (p1, e1, e2), if it does
+pub(super) fn intersect_triangle(
+    p1: &Point,
+    e1: &Vec4,
+    e2: &Vec4,
+    ray: &Ray,
+) -> Option<(f64, f64, f64)> {
+    let dir_cross_e2 = ray.direction.cross(e2);
+    let det = e1.dot(&dir_cross_e2);
+    // the ray is parallel to the triangle
+    if det.abs() < EPSILON {
+        return None;
+    }
+    let f = 1.0 / det;
+    let p1_to_origin = &ray.origin - p1;
+    let u = f * p1_to_origin.dot(&dir_cross_e2);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let origin_cross_e1 = p1_to_origin.cross(e1);
+    let v = f * ray.direction.dot(&origin_cross_e1);
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    Some((f * e2.dot(&origin_cross_e1), u, v))
+}
+
+impl Shape for Triangle {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        match intersect_triangle(&self.p1, &self.e1, &self.e2, ray) {
+            Some((t, u, v)) => vec![Intersection::with_uv(t, self, u, v)],
+            None => vec![],
+        }
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        bounds.add_point(&self.p1);
+        bounds.add_point(&self.p2);
+        bounds.add_point(&self.p3);
+        bounds
+    }
+
+    fn local_normal_at(&self, _point: &Point, _hit: Option<&Intersection>) -> Vec4 {
+        // a flat triangle has the same normal everywhere
+        self.normal.clone()
+    }
+}
@@ -0,0 +1,217 @@
+use std::path::Path;
+
+use crate::{
+    matters::{group::Group, smooth_triangle::SmoothTriangle, triangle::Triangle},
+    vector::{Point, Vec4},
+};
+
+/// NOTES:
+/// 1. supported statements are `v x y z`, `vn x y z`, `vt u v`, `f ...` and `g name`, everything else is ignored.
+/// 2. indices in a face start from 1, negative indices count back from the last vertex (or normal) read so far.
+/// 3. a face with more than 3 vertices is fan triangulated i.e (1, 2, 3), (1, 3, 4), (1, 4, 5), ...
+/// 4. a face which has a normal for every vertex gives smooth triangles, otherwise flat triangles.
+/// 5. a bad line never panics, it is counted in `ignored_lines` or recorded in `malformed_lines`.
+#[derive(Debug, Default)]
+pub struct ObjParser {
+    pub vertices: Vec<Point>,
+    pub normals: Vec<Vec4>,
+    pub texture_coords: Vec<(f64, f64)>,
+    // triangles which are not in any named group
+    pub default_group: Group,
+    // named groups in the order they first appear in the file
+    pub groups: Vec<(String, Group)>,
+    // lines with statements which are not supported (blank lines and comments are not counted)
+    pub ignored_lines: usize,
+    // line numbers (starting from 1) of supported statements which could not be parsed
+    pub malformed_lines: Vec<usize>,
+    // index in groups of the group which is receiving faces, None for the default group
+    current_group: Option<usize>,
+}
+
+// parses exactly `N` numbers, extra or missing numbers make the statement malformed
+fn parse_numbers<const N: usize>(args: &[&str]) -> Option<[f64; N]> {
+    if args.len() != N {
+        return None;
+    }
+    let mut numbers = [0.0; N];
+    for (number, arg) in numbers.iter_mut().zip(args) {
+        *number = arg.parse().ok()?;
+    }
+    Some(numbers)
+}
+
+// converts an obj index (1 based, or negative from the end) to a index in a list of length len
+fn resolve_index(index: &str, len: usize) -> Option<usize> {
+    let index = index.parse::<i64>().ok()?;
+    let len = len as i64;
+    if index > 0 && index <= len {
+        Some((index - 1) as usize)
+    } else if index < 0 && -index <= len {
+        Some((len + index) as usize)
+    } else {
+        None
+    }
+}
+
+impl ObjParser {
+    pub fn parse(content: &str) -> Self {
+        let mut parser = Self::default();
+        for (line_number, line) in content.lines().enumerate() {
+            let mut tokens = line.split_whitespace();
+            let Some(statement) = tokens.next() else {
+                continue;
+            };
+            let args = tokens.collect::<Vec<&str>>();
+            let parsed = match statement {
+                _ if statement.starts_with('#') => continue,
+                "v" => parser.parse_vertex(&args),
+                "vn" => parser.parse_normal(&args),
+                "vt" => parser.parse_texture_coord(&args),
+                "f" => parser.parse_face(&args),
+                "g" => parser.parse_group(&args),
+                _ => {
+                    parser.ignored_lines += 1;
+                    continue;
+                }
+            };
+            if parsed.is_none() {
+                parser.malformed_lines.push(line_number + 1);
+            }
+        }
+        parser
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    // all the triangles under one group, each named group becomes a child group
+    // meshes have a lot of triangles so every group gets a bounding volume hierarchy
+    pub fn into_group(self) -> Group {
+        let mut group = self.default_group;
+        for (_, mut named_group) in self.groups {
+            if !named_group.is_empty() {
+                named_group.build_bvh();
+                group.add_child(Box::new(named_group));
+            }
+        }
+        group.build_bvh();
+        group
+    }
+
+    // summary of what was read, to tell if a model was only partly understood
+    pub fn report(&self) -> String {
+        let triangles = self.default_group.children().len()
+            + self
+                .groups
+                .iter()
+                .map(|(_, group)| group.children().len())
+                .sum::<usize>();
+        format!(
+            "{} vertices, {} normals, {} texture coordinates, {} triangles, {} groups, {} ignored lines, {} malformed lines {:?}",
+            self.vertices.len(),
+            self.normals.len(),
+            self.texture_coords.len(),
+            triangles,
+            self.groups.len(),
+            self.ignored_lines,
+            self.malformed_lines.len(),
+            self.malformed_lines
+        )
+    }
+
+    fn parse_vertex(&mut self, args: &[&str]) -> Option<()> {
+        // the optional w is dropped, points are always w = 1
+        let [x, y, z] = match args.len() {
+            4 => parse_numbers::<3>(&args[..3])?,
+            _ => parse_numbers::<3>(args)?,
+        };
+        self.vertices.push(Point::new(x, y, z));
+        Some(())
+    }
+
+    fn parse_normal(&mut self, args: &[&str]) -> Option<()> {
+        let [x, y, z] = parse_numbers::<3>(args)?;
+        self.normals.push(Vec4::new(x, y, z));
+        Some(())
+    }
+
+    fn parse_texture_coord(&mut self, args: &[&str]) -> Option<()> {
+        // v and w are optional
+        let (u, v) = match args.len() {
+            1 => (parse_numbers::<1>(args)?[0], 0.0),
+            2 => parse_numbers::<2>(args).map(|[u, v]| (u, v))?,
+            3 => parse_numbers::<2>(&args[..2]).map(|[u, v]| (u, v))?,
+            _ => return None,
+        };
+        self.texture_coords.push((u, v));
+        Some(())
+    }
+
+    fn parse_group(&mut self, args: &[&str]) -> Option<()> {
+        if args.is_empty() {
+            // faces after a `g` without a name go back to the default group
+            self.current_group = None;
+            return Some(());
+        }
+        let name = args.join(" ");
+        let index = match self.groups.iter().position(|(n, _)| *n == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push((name, Group::default()));
+                self.groups.len() - 1
+            }
+        };
+        self.current_group = Some(index);
+        Some(())
+    }
+
+    // each vertex of a face is `v`, `v/vt`, `v//vn` or `v/vt/vn`
+    fn parse_face(&mut self, args: &[&str]) -> Option<()> {
+        if args.len() < 3 {
+            return None;
+        }
+        let mut vertices = Vec::with_capacity(args.len());
+        let mut normals = Vec::with_capacity(args.len());
+        for arg in args {
+            let mut indices = arg.split('/');
+            let vertex = resolve_index(indices.next()?, self.vertices.len())?;
+            // the texture index is only checked, triangles don't use it
+            if let Some(texture) = indices.next().filter(|t| !t.is_empty()) {
+                resolve_index(texture, self.texture_coords.len())?;
+            }
+            if let Some(normal) = indices.next().filter(|n| !n.is_empty()) {
+                normals.push(resolve_index(normal, self.normals.len())?);
+            }
+            if indices.next().is_some() {
+                return None;
+            }
+            vertices.push(vertex);
+        }
+        let smooth = normals.len() == vertices.len();
+        let group = match self.current_group {
+            Some(index) => &mut self.groups[index].1,
+            None => &mut self.default_group,
+        };
+        for i in 1..vertices.len() - 1 {
+            let (p1, p2, p3) = (
+                self.vertices[vertices[0]].clone(),
+                self.vertices[vertices[i]].clone(),
+                self.vertices[vertices[i + 1]].clone(),
+            );
+            if smooth {
+                group.add_child(Box::new(SmoothTriangle::new(
+                    p1,
+                    p2,
+                    p3,
+                    self.normals[normals[0]].clone(),
+                    self.normals[normals[i]].clone(),
+                    self.normals[normals[i + 1]].clone(),
+                )));
+            } else {
+                group.add_child(Box::new(Triangle::new(p1, p2, p3)));
+            }
+        }
+        Some(())
+    }
+}
@@ -0,0 +1,25 @@
+// NOTES:
+// 1. there are no dependencies, so pseudo random numbers come from hashing a seed with splitmix64.
+// 2. the same seed always gives the same number, so a seed made from what is being computed (like a point and a sample index)
+//    gives the same image every render no matter how many threads render it or in what order.
+
+// splitmix64, it turns any number into a well mixed one
+pub fn mix(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// one seed made from a list of values, the order of the values matters
+pub fn seed(values: &[u64]) -> u64 {
+    values.iter().fold(0, |seed, value| {
+        mix(seed ^ value.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+    })
+}
+
+// pseudo random number from 0 (included) to 1 (not included)
+pub fn unit_f64(seed: u64) -> f64 {
+    // the top 53 bits fill the mantissa of a f64
+    (mix(seed) >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -7,6 +7,8 @@ use crate::{
 pub struct Ray {
     pub origin: Point,
     pub direction: Vec4,
+    // when the ray was shot (while the camera's shutter is open), moving shapes are where they are at that time
+    pub time: f64,
 }
 
 impl AsRef<Ray> for Ray {
@@ -17,7 +19,15 @@ impl AsRef<Ray> for Ray {
 
 impl Ray {
     pub fn new(origin: Point, direction: Vec4) -> Self {
-        Self { origin, direction }
+        Self {
+            origin,
+            direction,
+            time: 0.0,
+        }
+    }
+    pub fn with_time(mut self, time: f64) -> Self {
+        self.time = time;
+        self
     }
     // t -> time, distance, position of ray at some time, distance t
     pub fn position(&self, t: f64) -> Point {
@@ -30,5 +40,6 @@ impl Ray {
             trasformation.as_ref() * self.origin.as_ref(),
             trasformation.as_ref() * self.direction.as_ref(),
         )
+        .with_time(self.time)
     }
 }
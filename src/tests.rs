@@ -1,10 +1,25 @@
+mod background;
+mod bounds;
+mod bvh;
 mod camera;
 mod canvas;
+mod cone;
+mod csg;
+mod cube;
+mod cylinder;
+mod group;
 mod light;
 mod material;
 mod matrix;
+mod motion;
+mod obj_parser;
+mod pattern;
+mod plane;
 mod points_and_vectors;
 mod ray;
+mod sampling;
 mod sphere;
+mod texture;
 mod transformation;
+mod triangle;
 mod world;
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use crate::{
+    canvas::Color,
+    matrix::Matrix,
+    matters::{
+        background::Background,
+        pattern::Pattern,
+        plane::Plane,
+        texture::{CubeMap, UvCheckers, UvPattern},
+        Shape,
+    },
+    ray::Ray,
+    vector::{Point, Vec4},
+    world::World,
+};
+
+// the color is the uv coordinate, to check where the background is looked up
+#[derive(Debug)]
+struct UvTestPattern;
+
+impl UvPattern for UvTestPattern {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        Color::new(u, v, 0.0)
+    }
+}
+
+fn solid(color: Color) -> Arc<dyn UvPattern> {
+    Arc::new(UvCheckers::new(1.0, 1.0, color.clone(), color))
+}
+
+#[test]
+fn the_default_background_is_black() {
+    let world = World::new();
+    assert!(matches!(world.background, Background::Solid(_)));
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(world.color_at(&ray), Color::black());
+}
+
+#[test]
+fn a_ray_which_misses_gets_the_background_color() {
+    let mut world = World::default();
+    world.background = Background::Solid(Color::new(0.2, 0.4, 0.8));
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 1.0, 0.0));
+    assert_eq!(world.color_at(&ray), Color::new(0.2, 0.4, 0.8));
+}
+
+#[test]
+fn a_vertical_gradient_blends_from_bottom_to_top() {
+    let background = Background::VerticalGradient {
+        top: Color::new(0.0, 0.0, 1.0),
+        bottom: Color::white(),
+    };
+    assert_eq!(
+        background.color_for(&Vec4::new(0.0, 1.0, 0.0)),
+        Color::new(0.0, 0.0, 1.0)
+    );
+    assert_eq!(
+        background.color_for(&Vec4::new(0.0, -1.0, 0.0)),
+        Color::white()
+    );
+    // the direction doesn't have to be normalized
+    assert_eq!(
+        background.color_for(&Vec4::new(0.0, 0.0, 3.0)),
+        Color::new(0.5, 0.5, 1.0)
+    );
+}
+
+#[test]
+fn a_cube_map_background_uses_the_face_the_ray_points_at() {
+    let background = Background::CubeMap(CubeMap::new(
+        solid(Color::new(1.0, 0.0, 0.0)),
+        Arc::new(UvTestPattern),
+        solid(Color::new(0.0, 0.0, 1.0)),
+        solid(Color::white()),
+        solid(Color::new(1.0, 1.0, 0.0)),
+        solid(Color::black()),
+    ));
+    assert_eq!(
+        background.color_for(&Vec4::new(-5.0, 1.0, 2.0)),
+        Color::new(1.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        background.color_for(&Vec4::new(0.5, 0.3, -0.1)),
+        Color::new(0.0, 0.0, 1.0)
+    );
+    assert_eq!(
+        background.color_for(&Vec4::new(0.0, 2.0, 1.0)),
+        Color::new(1.0, 1.0, 0.0)
+    );
+    // the direction is scaled to end on the front face, at (0.5, -0.5, 1)
+    assert_eq!(
+        background.color_for(&Vec4::new(1.0, -1.0, 2.0)),
+        Color::new(0.75, 0.25, 0.0)
+    );
+}
+
+#[test]
+fn a_rotated_cube_map_background_turns_it_s_faces() {
+    let mut cube_map = CubeMap::new(
+        solid(Color::new(1.0, 0.0, 0.0)),
+        solid(Color::new(0.0, 1.0, 0.0)),
+        solid(Color::new(0.0, 0.0, 1.0)),
+        solid(Color::white()),
+        solid(Color::new(1.0, 1.0, 0.0)),
+        solid(Color::black()),
+    );
+    // a quarter turn around y takes the front face (+z) to +x and the left face (-x) to +z
+    cube_map.set_transformation(Matrix::rotation_y_mat_4x4(std::f64::consts::FRAC_PI_2));
+    let background = Background::CubeMap(cube_map);
+    assert_eq!(
+        background.color_for(&Vec4::new(1.0, 0.0, 0.0)),
+        Color::new(0.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        background.color_for(&Vec4::new(0.0, 0.0, 1.0)),
+        Color::new(1.0, 0.0, 0.0)
+    );
+    // up stays up
+    assert_eq!(
+        background.color_for(&Vec4::new(0.0, 1.0, 0.0)),
+        Color::new(1.0, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn an_equirectangular_background_maps_directions_to_the_image() {
+    let background = Background::Equirectangular(Arc::new(UvTestPattern));
+    // u goes around the y axis starting behind the camera and growing to the right, v from straight down to straight up
+    assert_eq!(
+        background.color_for(&Vec4::new(0.0, 0.0, 1.0)),
+        Color::new(0.5, 0.5, 0.0)
+    );
+    assert_eq!(
+        background.color_for(&Vec4::new(2.0, 0.0, 0.0)),
+        Color::new(0.75, 0.5, 0.0)
+    );
+    assert_eq!(
+        background.color_for(&Vec4::new(-2.0, 0.0, 0.0)),
+        Color::new(0.25, 0.5, 0.0)
+    );
+    assert_eq!(
+        background.color_for(&Vec4::new(0.0, 1.0, 0.0)),
+        Color::new(0.5, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn a_mirror_reflects_the_background() {
+    let mut world = World::new();
+    world.lights = World::default().lights;
+    world.background = Background::VerticalGradient {
+        top: Color::new(0.0, 0.0, 1.0),
+        bottom: Color::black(),
+    };
+    let mut mirror = Plane::new(Matrix::translation_mat_4x4(0.0, -1.0, 0.0));
+    mirror.material.color = Color::black();
+    mirror.material.specular = 0.0;
+    mirror.material.reflective = 1.0;
+    world.set_objects(vec![Box::new(mirror)]);
+    // straight down into the mirror, the reflection is straight up
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, -1.0, 0.0));
+    assert_eq!(world.color_at(&ray), Color::new(0.0, 0.0, 1.0));
+}
@@ -0,0 +1,159 @@
+use crate::{
+    matrix::Matrix,
+    matters::{
+        bounds::BoundingBox, cone::Cone, cube::Cube, cylinder::Cylinder, group::Group,
+        plane::Plane, sphere::Sphere, triangle::Triangle, Shape,
+    },
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+#[test]
+fn adding_points_to_an_empty_bounding_box() {
+    let mut bounds = BoundingBox::empty();
+    assert!(bounds.is_empty());
+    bounds.add_point(&Point::new(-5.0, 2.0, 0.0));
+    bounds.add_point(&Point::new(7.0, 0.0, -3.0));
+    assert_eq!(bounds.min, Point::new(-5.0, 0.0, -3.0));
+    assert_eq!(bounds.max, Point::new(7.0, 2.0, 0.0));
+}
+
+#[test]
+fn bounds_of_the_primitives() {
+    let unit = BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+    assert_eq!(Sphere::default().bounds(), unit);
+    assert_eq!(Cube::default().bounds(), unit);
+    let plane = Plane::default().bounds();
+    assert_eq!(
+        (plane.min.0, plane.min.1, plane.max.1, plane.max.2),
+        (f64::NEG_INFINITY, 0.0, 0.0, f64::INFINITY)
+    );
+    let cylinder = Cylinder {
+        minimum: -5.0,
+        maximum: 3.0,
+        ..Cylinder::default()
+    };
+    assert_eq!(
+        cylinder.bounds(),
+        BoundingBox::new(Point::new(-1.0, -5.0, -1.0), Point::new(1.0, 3.0, 1.0))
+    );
+    let cone = Cone {
+        minimum: -5.0,
+        maximum: 3.0,
+        ..Cone::default()
+    };
+    assert_eq!(
+        cone.bounds(),
+        BoundingBox::new(Point::new(-5.0, -5.0, -5.0), Point::new(5.0, 3.0, 5.0))
+    );
+    let triangle = Triangle::new(
+        Point::new(-3.0, 7.0, 2.0),
+        Point::new(6.0, 2.0, -4.0),
+        Point::new(2.0, -1.0, -1.0),
+    );
+    assert_eq!(
+        triangle.bounds(),
+        BoundingBox::new(Point::new(-3.0, -1.0, -4.0), Point::new(6.0, 7.0, 2.0))
+    );
+}
+
+#[test]
+fn merging_and_containing_bounding_boxes() {
+    let mut bounds = BoundingBox::new(Point::new(-5.0, -2.0, 0.0), Point::new(7.0, 4.0, 4.0));
+    let other = BoundingBox::new(Point::new(8.0, -7.0, -2.0), Point::new(14.0, 2.0, 8.0));
+    bounds.merge(&other);
+    assert_eq!(
+        bounds,
+        BoundingBox::new(Point::new(-5.0, -7.0, -2.0), Point::new(14.0, 4.0, 8.0))
+    );
+    assert!(bounds.contains_box(&other));
+    assert!(bounds.contains_point(&Point::new(14.0, 4.0, 8.0)));
+    assert!(!bounds.contains_point(&Point::new(15.0, 0.0, 0.0)));
+}
+
+#[test]
+fn transforming_a_bounding_box() {
+    let bounds = BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+    let transformed = bounds.transform(
+        &Matrix::rotation_y_mat_4x4(std::f64::consts::FRAC_PI_4)
+            .rotation_x_mat_4x4_chain(std::f64::consts::FRAC_PI_4),
+    );
+    assert_eq!(
+        transformed.min,
+        Point::new(-std::f64::consts::SQRT_2, -1.70711, -1.70711)
+    );
+    assert_eq!(
+        transformed.max,
+        Point::new(std::f64::consts::SQRT_2, 1.70711, 1.70711)
+    );
+    // infinite boxes can't be transformed corner by corner
+    assert!(!Plane::default()
+        .bounds()
+        .transform(&Matrix::rotation_x_mat_4x4(1.0))
+        .is_finite());
+}
+
+#[test]
+fn bounds_of_a_shape_in_parent_space() {
+    let sphere = Sphere::new(
+        Matrix::scaling_mat_4x4(0.5, 2.0, 4.0).translation_mat_4x4_chain(1.0, -3.0, 5.0),
+    );
+    assert_eq!(
+        sphere.parent_space_bounds(),
+        BoundingBox::new(Point::new(0.5, -5.0, 1.0), Point::new(1.5, -1.0, 9.0))
+    );
+}
+
+#[test]
+fn a_group_caches_the_bounds_of_its_children() {
+    let mut group = Group::default();
+    group.add_child(Box::new(Sphere::new(
+        Matrix::scaling_mat_4x4(2.0, 2.0, 2.0).translation_mat_4x4_chain(2.0, 5.0, -3.0),
+    )));
+    group.add_child(Box::new(Cylinder {
+        transformation: Matrix::scaling_mat_4x4(0.5, 1.0, 0.5)
+            .translation_mat_4x4_chain(-4.0, -1.0, 4.0),
+        minimum: -2.0,
+        maximum: 2.0,
+        ..Cylinder::default()
+    }));
+    assert_eq!(
+        group.bounds(),
+        BoundingBox::new(Point::new(-4.5, -3.0, -5.0), Point::new(4.0, 7.0, 4.5))
+    );
+}
+
+#[test]
+fn intersecting_a_ray_with_a_bounding_box() {
+    let bounds = BoundingBox::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+    let cases = [
+        ((15.0, 1.0, 2.0), (-1.0, 0.0, 0.0), true),
+        ((-5.0, -1.0, 4.0), (1.0, 0.0, 0.0), true),
+        ((7.0, 6.0, 5.0), (0.0, -1.0, 0.0), true),
+        ((9.0, -5.0, 6.0), (0.0, 1.0, 0.0), true),
+        ((8.0, 2.0, 12.0), (0.0, 0.0, -1.0), true),
+        ((6.0, 0.0, -5.0), (0.0, 0.0, 1.0), true),
+        ((8.0, 1.0, 3.5), (0.0, 0.0, 1.0), true),
+        ((9.0, -1.0, -8.0), (2.0, 4.0, 6.0), false),
+        ((8.0, 3.0, -4.0), (6.0, 2.0, 4.0), false),
+        ((9.0, -1.0, -2.0), (4.0, 6.0, 2.0), false),
+        ((4.0, 0.0, 9.0), (0.0, 0.0, -1.0), false),
+        ((8.0, 6.0, -1.0), (0.0, -1.0, 0.0), false),
+        ((12.0, 5.0, 4.0), (-1.0, 0.0, 0.0), false),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz), result) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz).normalize());
+        assert_eq!(bounds.intersects(&ray), result);
+    }
+}
+
+#[test]
+fn a_ray_missing_the_group_bounds_does_not_test_the_children() {
+    let mut group = Group::default();
+    group.add_child(Box::new(Sphere::default()));
+    // the bounds are cached, so a ray that misses them gets nothing even from a child that would be hit
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 1.0, 0.0));
+    assert!(group.local_intersect(&ray).is_empty());
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(group.local_intersect(&ray).len(), 2);
+}
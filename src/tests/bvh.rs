@@ -0,0 +1,151 @@
+use crate::{
+    bvh::Bvh,
+    matrix::Matrix,
+    matters::{group::Group, plane::Plane, sphere::Sphere, Shape},
+    ray::Ray,
+    vector::{Point, Vec4},
+    world::World,
+};
+
+// a grid of n x n small spheres on the xz plane
+fn sphere_grid(n: usize) -> Vec<Box<dyn Shape>> {
+    let mut objects: Vec<Box<dyn Shape>> = Vec::new();
+    for row in 0..n {
+        for col in 0..n {
+            objects.push(Box::new(Sphere::new(
+                Matrix::scaling_mat_4x4(0.4, 0.4, 0.4)
+                    .translation_mat_4x4_chain(col as f64, 0.0, row as f64),
+            )));
+        }
+    }
+    objects
+}
+
+#[test]
+fn a_bvh_over_no_shapes_is_empty() {
+    let bvh = Bvh::build(&[]);
+    assert!(bvh.is_empty());
+    assert_eq!(bvh.depth(), 0);
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, 0.0, 1.0));
+    assert!(bvh.intersect(&[], &ray).is_empty());
+}
+
+#[test]
+fn a_bvh_is_logarithmic_in_depth() {
+    let objects = sphere_grid(32);
+    let bvh = Bvh::build(&objects);
+    assert_eq!(bvh.len(), 1024);
+    // 1024 shapes in leaves of at most 4 need 8 levels of splitting
+    assert_eq!(bvh.depth(), 9);
+}
+
+#[test]
+fn a_bvh_gives_the_same_intersections_as_testing_every_shape() {
+    let mut objects = sphere_grid(10);
+    objects.push(Box::new(Plane::new(Matrix::translation_mat_4x4(
+        0.0, -1.0, 0.0,
+    ))));
+    let bvh = Bvh::build(&objects);
+    let rays = [
+        Ray::new(Point::new(4.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0)),
+        Ray::new(Point::new(-5.0, 0.0, 3.0), Vec4::new(1.0, 0.0, 0.0)),
+        Ray::new(
+            Point::new(-2.0, 3.0, -2.0),
+            Vec4::new(1.0, -0.3, 1.0).normalize(),
+        ),
+        Ray::new(Point::new(4.5, 10.0, 4.5), Vec4::new(0.0, -1.0, 0.0)),
+        Ray::new(Point::new(4.0, 0.0, 4.0), Vec4::new(0.0, 0.0, 1.0)),
+    ];
+    for ray in rays {
+        let mut expected = objects
+            .iter()
+            .flat_map(|object| object.intersect(&ray))
+            .map(|i| i.distance)
+            .collect::<Vec<f64>>();
+        expected.sort_by(f64::total_cmp);
+        let mut actual = bvh
+            .intersect(&objects, &ray)
+            .iter()
+            .map(|i| i.distance)
+            .collect::<Vec<f64>>();
+        actual.sort_by(f64::total_cmp);
+        assert!(!expected.is_empty());
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn world_intersect_uses_the_bvh() {
+    let mut world = World::default();
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    world.build_bvh();
+    assert_eq!(
+        world
+            .world_intersect(&ray)
+            .iter()
+            .map(|i| i.distance)
+            .collect::<Vec<f64>>(),
+        vec![4.0, 4.5, 5.5, 6.0]
+    );
+    // adding an object drops the tree instead of missing the new object
+    world.add_object(Box::new(Sphere::new(Matrix::translation_mat_4x4(
+        0.0, 0.0, 3.0,
+    ))));
+    assert_eq!(world.world_intersect(&ray).len(), 6);
+}
+
+#[test]
+fn replacing_the_objects_drops_the_bvh_of_the_world() {
+    let mut world = World::new();
+    world.set_objects(sphere_grid(10));
+    world.build_bvh();
+    // fewer objects than the tree was built for, an outdated tree would look up objects which are gone
+    world.set_objects(World::default().objects_mut().drain(..).collect());
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(
+        world
+            .world_intersect(&ray)
+            .iter()
+            .map(|i| i.distance)
+            .collect::<Vec<f64>>(),
+        vec![4.0, 4.5, 5.5, 6.0]
+    );
+}
+
+#[test]
+fn changing_an_object_drops_the_bvh_of_the_world() {
+    let mut world = World::default();
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    world.build_bvh();
+    // the same number of objects, but the inner sphere is now far away from the tree's box
+    world.objects_mut()[1] = Box::new(Sphere::new(Matrix::translation_mat_4x4(10.0, 0.0, 0.0)));
+    let ray_to_moved = Ray::new(Point::new(10.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(world.world_intersect(&ray_to_moved).len(), 2);
+    assert_eq!(world.world_intersect(&ray).len(), 2);
+}
+
+#[test]
+fn a_group_with_a_bvh_intersects_like_one_without() {
+    let mut group = Group::default();
+    for object in sphere_grid(8) {
+        group.add_child(object);
+    }
+    let ray = Ray::new(
+        Point::new(-1.0, 0.0, -1.0),
+        Vec4::new(1.0, 0.0, 1.0).normalize(),
+    );
+    let without = group
+        .intersect(&ray)
+        .iter()
+        .map(|i| i.distance)
+        .collect::<Vec<f64>>();
+    group.build_bvh();
+    let with = group
+        .intersect(&ray)
+        .iter()
+        .map(|i| i.distance)
+        .collect::<Vec<f64>>();
+    // the diagonal goes through all 8 spheres on it
+    assert_eq!(without.len(), 16);
+    assert_eq!(with, without);
+}
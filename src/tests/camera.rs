@@ -1,7 +1,17 @@
+use std::sync::Arc;
+
 use crate::{
     canvas::Color,
     matrix::Matrix,
-    matters::camera::Camera,
+    matters::{
+        camera::{Camera, Projection},
+        light::Light,
+        pattern::{Gradient, Pattern, Stripe},
+        plane::Plane,
+        sampling::{AdaptiveSampling, ApertureShape, Filter, SamplePattern},
+        sphere::Sphere,
+        Shape,
+    },
     vector::{Point, Vec4},
     world::World,
 };
@@ -77,3 +87,407 @@ fn rendering_a_world_with_a_camera() {
         Color::new(0.38066, 0.47583, 0.2855)
     );
 }
+
+#[test]
+fn rendering_with_many_threads_gives_the_same_image() {
+    let world = World::default();
+    let mut camera = Camera::new(21, 13, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -5.0),
+        Point::new(0.0, 0.0, 0.0),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    camera.threads = 1;
+    let serial = camera.render(&world);
+    // more threads than rows too, the extra workers have nothing to do
+    for threads in [2, 4, 32] {
+        camera.threads = threads;
+        let image = camera.render(&world);
+        for y in 0..camera.vsize {
+            for x in 0..camera.hsize {
+                assert_eq!(image.pixel_at((x, y)).rgb(), serial.pixel_at((x, y)).rgb());
+            }
+        }
+    }
+}
+
+#[test]
+fn the_exposure_scales_the_rendered_colors() {
+    let world = World::default();
+    let mut camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    assert_eq!(camera.exposure, 1.0);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -5.0),
+        Point::new(0.0, 0.0, 0.0),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    camera.set_exposure_stops(-1.0);
+    assert_eq!(camera.exposure, 0.5);
+    let image = camera.render(&world);
+    assert_eq!(
+        *image.pixel_at((5, 5)),
+        Color::new(0.19033, 0.237915, 0.14275)
+    );
+}
+
+// white where the sphere is and black around it, so a pixel's color is how much of it the sphere covers
+fn flat_sphere_world() -> World {
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(0.0, 0.0, -10.0), Color::white())];
+    let mut sphere = Sphere::default();
+    sphere.material.ambient = 1.0;
+    sphere.material.diffuse = 0.0;
+    sphere.material.specular = 0.0;
+    world.set_objects(vec![Box::new(sphere)]);
+    world
+}
+
+#[test]
+fn a_single_sample_goes_through_the_center_of_the_pixel() {
+    let world = World::default();
+    let camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    assert_eq!(camera.samples, 1);
+    for (x, y) in [(0, 0), (5, 5), (3, 7)] {
+        assert_eq!(
+            camera.color_for_pixel(&world, x, y),
+            world.color_at(&camera.ray_for_pixel(x, y))
+        );
+    }
+}
+
+#[test]
+fn supersampling_smooths_the_edges_of_objects() {
+    let world = flat_sphere_world();
+    let mut camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -2.0),
+        Point::origin(),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    // the edge of the sphere goes through pixel (8, 5), it's center is on the sphere
+    assert_eq!(camera.color_for_pixel(&world, 8, 5), Color::white());
+    camera.samples = 64;
+    for sample_pattern in [
+        SamplePattern::Grid,
+        SamplePattern::Stratified,
+        SamplePattern::Jittered,
+    ] {
+        for filter in [
+            Filter::Box,
+            Filter::Tent,
+            Filter::Gaussian,
+            Filter::Mitchell,
+        ] {
+            camera.sample_pattern = sample_pattern;
+            camera.filter = filter;
+            let color = camera.color_for_pixel(&world, 8, 5);
+            assert!(color.red() > 0.0 && color.red() < 1.0);
+            assert_eq!(color.red(), color.green());
+            // the middle of the sphere is white whatever the samples are
+            assert_eq!(camera.color_for_pixel(&world, 5, 5), Color::white());
+        }
+    }
+}
+
+#[test]
+fn supersampled_renders_are_the_same_with_any_number_of_threads() {
+    let world = flat_sphere_world();
+    let mut camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -2.0),
+        Point::origin(),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    camera.samples = 9;
+    camera.sample_pattern = SamplePattern::Stratified;
+    camera.filter = Filter::Mitchell;
+    camera.threads = 1;
+    let serial = camera.render(&world);
+    camera.threads = 4;
+    let image = camera.render(&world);
+    for y in 0..camera.vsize {
+        for x in 0..camera.hsize {
+            assert_eq!(image.pixel_at((x, y)).rgb(), serial.pixel_at((x, y)).rgb());
+        }
+    }
+}
+
+#[test]
+fn adaptive_sampling_adds_rays_only_where_the_colors_change() {
+    let world = flat_sphere_world();
+    let mut camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -2.0),
+        Point::origin(),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    camera.threads = 1;
+    camera.adaptive = Some(AdaptiveSampling {
+        max_samples: 16,
+        threshold: 0.1,
+    });
+    let (image, heatmap) = camera.render_with_heatmap(&world);
+    // far from the edge of the sphere every pixel keeps it's single ray
+    let fewest = Color::new(1.0 / 16.0, 0.0, 15.0 / 16.0);
+    assert_eq!(heatmap.pixel_at((0, 0)), &fewest);
+    assert_eq!(heatmap.pixel_at((5, 5)), &fewest);
+    assert_eq!(image.pixel_at((5, 5)), &Color::white());
+    assert_eq!(image.pixel_at((0, 0)), &Color::black());
+    // the pixel on the edge stands out from it's neighbours
+    assert!(heatmap.pixel_at((8, 5)).red() > 0.5);
+    let edge = image.pixel_at((8, 5));
+    assert!(edge.red() > 0.0 && edge.red() < 1.0);
+    // the same image with any number of threads
+    camera.threads = 4;
+    let (threaded, _) = camera.render_with_heatmap(&world);
+    for y in 0..camera.vsize {
+        for x in 0..camera.hsize {
+            assert_eq!(
+                threaded.pixel_at((x, y)).rgb(),
+                image.pixel_at((x, y)).rgb()
+            );
+        }
+    }
+}
+
+#[test]
+fn adaptive_sampling_stops_once_the_rays_agree() {
+    // a wall slowly going from black to white, a pixel differs from it's neighbours but it's rays hardly vary
+    let mut gradient = Gradient::new(Color::black(), Color::white());
+    gradient.set_transformation(
+        Matrix::scaling_mat_4x4(4.0, 1.0, 1.0).translation_mat_4x4_chain(-2.0, 0.0, 0.0),
+    );
+    let mut wall = Plane::new(Matrix::rotation_x_mat_4x4(std::f64::consts::FRAC_PI_2));
+    wall.material.pattern = Some(Arc::new(gradient));
+    wall.material.ambient = 1.0;
+    wall.material.diffuse = 0.0;
+    wall.material.specular = 0.0;
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(0.0, 0.0, -10.0), Color::white())];
+    world.set_objects(vec![Box::new(wall)]);
+    let mut camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -1.0),
+        Point::origin(),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    camera.adaptive = Some(AdaptiveSampling {
+        max_samples: 16,
+        threshold: 0.03,
+    });
+    let (_, heatmap) = camera.render_with_heatmap(&world);
+    // more rays than the first one, but far fewer than max_samples
+    let heat = heatmap.pixel_at((5, 5)).red();
+    assert!(heat > 1.0 / 16.0 && heat < 0.5);
+}
+
+#[test]
+fn adaptive_sampling_adds_nothing_to_a_flat_image() {
+    let world = World::new();
+    let mut camera = Camera::new(5, 4, std::f64::consts::FRAC_PI_2);
+    camera.samples = 4;
+    camera.adaptive = Some(AdaptiveSampling::default());
+    let (_, heatmap) = camera.render_with_heatmap(&world);
+    for y in 0..camera.vsize {
+        for x in 0..camera.hsize {
+            assert_eq!(
+                heatmap.pixel_at((x, y)),
+                &Color::new(4.0 / 64.0, 0.0, 60.0 / 64.0)
+            );
+        }
+    }
+}
+
+#[test]
+fn rays_from_anywhere_on_the_lens_meet_on_the_focal_plane() {
+    let mut camera = Camera::new(201, 101, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -5.0),
+        Point::origin(),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    camera.aperture = 0.5;
+    camera.focal_distance = 5.0;
+    for aperture_shape in [
+        ApertureShape::Disk,
+        ApertureShape::Polygon {
+            sides: 5,
+            rotation: 0.3,
+        },
+    ] {
+        camera.aperture_shape = aperture_shape;
+        for (x, y) in [(100.5, 50.5), (20.0, 80.0)] {
+            // the pinhole ray shows where the rays through the lens have to meet
+            let center = camera.ray_for_canvas_point(x, y, (0.0, 0.0));
+            let cos = center.direction.dot(&Vec4::new(0.0, 0.0, 1.0));
+            let focus = center.position(5.0 / cos);
+            for lens in [(1.0, 0.0), (0.0, -1.0), (-0.3, 0.4)] {
+                let ray = camera.ray_for_canvas_point(x, y, lens);
+                let start = &ray.origin - &Point::new(0.0, 0.0, -5.0);
+                assert!(start.magnitude() <= 0.5 + 0.00001);
+                assert_eq!(start.2, 0.0);
+                assert_eq!(ray.position((&focus - &ray.origin).magnitude()), focus);
+            }
+        }
+    }
+    // the pinhole ray through the center of the canvas still goes straight ahead
+    assert_eq!(
+        camera
+            .ray_for_canvas_point(100.5, 50.5, (0.0, 0.0))
+            .direction,
+        Vec4::new(0.0, 0.0, 1.0)
+    );
+}
+
+#[test]
+fn only_things_at_the_focal_distance_are_sharp() {
+    // a wall of black and white stripes one unit wide, the middle of the image is in the middle of a white one
+    let mut stripes = Stripe::new(Color::white(), Color::black());
+    stripes.set_transformation(Matrix::translation_mat_4x4(-0.5, 0.0, 0.0));
+    let mut wall = Plane::new(Matrix::rotation_x_mat_4x4(std::f64::consts::FRAC_PI_2));
+    wall.material.pattern = Some(Arc::new(stripes));
+    wall.material.ambient = 1.0;
+    wall.material.diffuse = 0.0;
+    wall.material.specular = 0.0;
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(0.0, 0.0, -10.0), Color::white())];
+    world.set_objects(vec![Box::new(wall)]);
+    let mut camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -2.0),
+        Point::origin(),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    camera.samples = 16;
+    camera.aperture = 1.0;
+    // every ray through the pixel lands in the white stripe
+    camera.focal_distance = 2.0;
+    assert_eq!(camera.color_for_pixel(&world, 5, 5), Color::white());
+    // the rays spread over the black stripes next to it
+    camera.focal_distance = 10.0;
+    let blurred = camera.color_for_pixel(&world, 5, 5);
+    assert!(blurred.red() > 0.0 && blurred.red() < 1.0);
+}
+
+#[test]
+fn orthographic_rays_are_parallel() {
+    let mut camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    camera.projection = Projection::Orthographic { size: 4.0 };
+    let center = camera.ray_for_pixel(5, 5);
+    assert_eq!(
+        (center.origin, center.direction),
+        (Point::origin(), Vec4::new(0.0, 0.0, -1.0))
+    );
+    // the left edge of the canvas is 2 units to the left (+x)
+    let left = camera.ray_for_canvas_point(0.0, 5.5, (0.0, 0.0));
+    assert_eq!(
+        (left.origin, left.direction),
+        (Point::new(2.0, 0.0, 0.0), Vec4::new(0.0, 0.0, -1.0))
+    );
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -5.0),
+        Point::origin(),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    let corner = camera.ray_for_canvas_point(0.0, 0.0, (0.0, 0.0));
+    assert_eq!(
+        (corner.origin, corner.direction),
+        (Point::new(-2.0, 2.0, -5.0), Vec4::new(0.0, 0.0, 1.0))
+    );
+}
+
+#[test]
+fn an_orthographic_view_does_not_shrink_far_things() {
+    let world = flat_sphere_world();
+    let mut camera = Camera::new(11, 11, std::f64::consts::FRAC_PI_2);
+    camera.projection = Projection::Orthographic { size: 4.0 };
+    camera.threads = 1;
+    let [near, far] = [5.0, 50.0].map(|distance| {
+        camera.transform = Matrix::view_transformation(
+            Point::new(0.0, 0.0, -distance),
+            Point::origin(),
+            Vec4::new(0.0, 1.0, 0.0),
+        );
+        camera.render(&world)
+    });
+    for y in 0..camera.vsize {
+        for x in 0..camera.hsize {
+            assert_eq!(near.pixel_at((x, y)), far.pixel_at((x, y)));
+        }
+    }
+    // the sphere (radius 1) covers half of the 4 units across the canvas
+    assert_eq!(near.pixel_at((4, 5)), &Color::white());
+    assert_eq!(near.pixel_at((1, 5)), &Color::black());
+}
+
+#[test]
+fn a_fisheye_sees_180_degrees_across() {
+    let mut camera = Camera::new(201, 101, std::f64::consts::PI);
+    camera.projection = Projection::Fisheye;
+    assert_eq!(
+        camera
+            .ray_for_canvas_point(100.5, 50.5, (0.0, 0.0))
+            .direction,
+        Vec4::new(0.0, 0.0, -1.0)
+    );
+    // the edges of the longer side look straight to the sides
+    assert_eq!(
+        camera.ray_for_canvas_point(0.0, 50.5, (0.0, 0.0)).direction,
+        Vec4::new(1.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        camera
+            .ray_for_canvas_point(201.0, 50.5, (0.0, 0.0))
+            .direction,
+        Vec4::new(-1.0, 0.0, 0.0)
+    );
+    // half way to the top is 45 degrees up
+    let half = std::f64::consts::FRAC_1_SQRT_2;
+    assert_eq!(
+        camera
+            .ray_for_canvas_point(100.5, 0.25, (0.0, 0.0))
+            .direction,
+        Vec4::new(0.0, half, -half)
+    );
+}
+
+#[test]
+fn an_equirectangular_panorama_sees_all_around() {
+    let mut camera = Camera::new(200, 100, std::f64::consts::FRAC_PI_2);
+    camera.projection = Projection::Equirectangular;
+    for (x, y, direction) in [
+        (100.0, 50.0, Vec4::new(0.0, 0.0, -1.0)),
+        // to the right
+        (150.0, 50.0, Vec4::new(-1.0, 0.0, 0.0)),
+        (50.0, 50.0, Vec4::new(1.0, 0.0, 0.0)),
+        // behind
+        (0.0, 50.0, Vec4::new(0.0, 0.0, 1.0)),
+        (200.0, 50.0, Vec4::new(0.0, 0.0, 1.0)),
+        // up and down
+        (100.0, 0.0, Vec4::new(0.0, 1.0, 0.0)),
+        (100.0, 100.0, Vec4::new(0.0, -1.0, 0.0)),
+        (100.0, 25.0, Vec4::new(0.0, 0.5f64.sqrt(), -0.5f64.sqrt())),
+    ] {
+        let ray = camera.ray_for_canvas_point(x, y, (0.0, 0.0));
+        assert_eq!((ray.origin, ray.direction), (Point::origin(), direction));
+    }
+}
+
+#[test]
+fn a_panorama_shows_things_behind_the_camera() {
+    let mut world = flat_sphere_world();
+    world.objects_mut()[0].set_transformation(Matrix::translation_mat_4x4(0.0, 0.0, 5.0));
+    let mut camera = Camera::new(40, 20, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::origin(),
+        Point::new(0.0, 0.0, -1.0),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    // a perspective camera looking the other way doesn't see the sphere
+    let image = camera.render(&world);
+    assert_eq!(image.pixel_at((20, 10)), &Color::black());
+    camera.projection = Projection::Equirectangular;
+    let image = camera.render(&world);
+    assert_eq!(image.pixel_at((0, 10)), &Color::white());
+    assert_eq!(image.pixel_at((20, 10)), &Color::black());
+}
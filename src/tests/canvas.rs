@@ -38,3 +38,89 @@ fn test_canvas_to_ppm() {
 "#
     );
 }
+
+#[test]
+fn reading_a_file_with_the_wrong_magic_number() {
+    let ppm = b"P32\n1 1\n255\n0 0 0\n";
+    assert!(Canvas::from_ppm(ppm).is_err());
+}
+
+#[test]
+fn reading_a_plain_ppm_returns_a_canvas() {
+    let ppm = b"P3\n4 3\n255\n\
+        255 127 0  0 127 255  127 255 0  255 255 255\n\
+        0 0 0  255 0 0  0 255 0  0 0 255\n\
+        255 255 0  0 255 255  255 0 255  127 127 127\n";
+    let canvas = Canvas::from_ppm(ppm).unwrap();
+    assert_eq!((canvas.width, canvas.height), (4, 3));
+    for (x, y, color) in [
+        (0, 0, Color::new(1.0, 0.49804, 0.0)),
+        (1, 0, Color::new(0.0, 0.49804, 1.0)),
+        (2, 0, Color::new(0.49804, 1.0, 0.0)),
+        (3, 0, Color::new(1.0, 1.0, 1.0)),
+        (0, 1, Color::new(0.0, 0.0, 0.0)),
+        (1, 1, Color::new(1.0, 0.0, 0.0)),
+        (2, 1, Color::new(0.0, 1.0, 0.0)),
+        (3, 1, Color::new(0.0, 0.0, 1.0)),
+        (0, 2, Color::new(1.0, 1.0, 0.0)),
+        (1, 2, Color::new(0.0, 1.0, 1.0)),
+        (2, 2, Color::new(1.0, 0.0, 1.0)),
+        (3, 2, Color::new(0.49804, 0.49804, 0.49804)),
+    ] {
+        assert_eq!(canvas.pixel_at((x, y)), &color);
+    }
+}
+
+#[test]
+fn ppm_parsing_ignores_comments_and_odd_whitespace() {
+    let ppm = b"P3\n# this is a comment\n2 1 # this, too\n255\n# another comment\n\t255 255 255\n# oh, no, comments in the pixel data!\n255    0\r\n  255\n";
+    let canvas = Canvas::from_ppm(ppm).unwrap();
+    assert_eq!(canvas.pixel_at((0, 0)), &Color::new(1.0, 1.0, 1.0));
+    assert_eq!(canvas.pixel_at((1, 0)), &Color::new(1.0, 0.0, 1.0));
+}
+
+#[test]
+fn ppm_values_are_scaled_by_maxval_and_rgb_triples_can_span_lines() {
+    let ppm = b"P3\n2 2\n100\n100 100 100  50\n50 50  75 50 25  0 0 0\n";
+    let canvas = Canvas::from_ppm(ppm).unwrap();
+    assert_eq!(canvas.pixel_at((0, 1)), &Color::new(0.75, 0.5, 0.25));
+    assert_eq!(canvas.pixel_at((1, 0)), &Color::new(0.5, 0.5, 0.5));
+}
+
+#[test]
+fn reading_a_binary_ppm() {
+    let mut ppm = b"P6 # binary\n2 1\n255\n".to_vec();
+    // the pixel bytes can look like whitespace or a comment
+    ppm.extend([255, 10, 35, 0, 128, 32]);
+    let canvas = Canvas::from_ppm(&ppm).unwrap();
+    assert_eq!(
+        canvas.pixel_at((0, 0)),
+        &Color::new(1.0, 10.0 / 255.0, 35.0 / 255.0)
+    );
+    assert_eq!(
+        canvas.pixel_at((1, 0)),
+        &Color::new(0.0, 128.0 / 255.0, 32.0 / 255.0)
+    );
+    // two bytes per value for a large maxval
+    let mut ppm = b"P6\n1 1\n1000\n".to_vec();
+    ppm.extend([3, 232, 1, 244, 0, 0]);
+    let canvas = Canvas::from_ppm(&ppm).unwrap();
+    assert_eq!(canvas.pixel_at((0, 0)), &Color::new(1.0, 0.5, 0.0));
+    // missing pixel bytes
+    assert!(Canvas::from_ppm(b"P6\n2 1\n255\n\x01\x02\x03").is_err());
+    // the header alone must not allocate the image
+    assert!(Canvas::from_ppm(b"P6\n4000000000 4000000000 255\n\x01\x02\x03").is_err());
+    assert!(Canvas::from_ppm(b"P3\n4000000000 4000000000 255\n1 2 3").is_err());
+    assert!(Canvas::from_ppm(b"P6\n18446744073709551615 2 255\n").is_err());
+}
+
+#[test]
+fn a_written_ppm_reads_back_the_same() {
+    let mut canvas = Canvas::new(3, 2);
+    canvas.write_pixel((0, 0), &Color::new(1.0, 0.0, 0.0));
+    canvas.write_pixel((2, 1), &Color::new(0.0, 0.0, 1.0));
+    let read = Canvas::from_ppm(canvas.to_ppm().as_bytes()).unwrap();
+    assert_eq!(read.pixel_at((0, 0)), &Color::new(1.0, 0.0, 0.0));
+    assert_eq!(read.pixel_at((2, 1)), &Color::new(0.0, 0.0, 1.0));
+    assert_eq!(read.pixel_at((1, 1)), &Color::black());
+}
@@ -0,0 +1,87 @@
+use crate::{
+    matters::{cone::Cone, Shape},
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+#[test]
+fn intersecting_a_cone_with_a_ray() {
+    let cone = Cone::default();
+    let cases = [
+        ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+        ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0), 8.66025, 8.66025),
+        ((1.0, 1.0, -5.0), (-0.5, -1.0, 1.0), 4.55006, 49.44994),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz), t0, t1) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz).normalize());
+        let xs = cone.local_intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert!((xs[0].distance - t0).abs() < 0.0001);
+        assert!((xs[1].distance - t1).abs() < 0.0001);
+    }
+}
+
+#[test]
+fn intersecting_a_cone_with_a_ray_parallel_to_one_of_its_halves() {
+    let cone = Cone::default();
+    let ray = Ray::new(
+        Point::new(0.0, 0.0, -1.0),
+        Vec4::new(0.0, 1.0, 1.0).normalize(),
+    );
+    let xs = cone.local_intersect(&ray);
+    assert_eq!(xs.len(), 1);
+    assert!((xs[0].distance - 0.35355).abs() < 0.00001);
+}
+
+#[test]
+fn intersecting_a_cones_end_caps() {
+    let cone = Cone {
+        minimum: -0.5,
+        maximum: 0.5,
+        closed: true,
+        ..Cone::default()
+    };
+    let cases = [
+        ((0.0, 0.0, -5.0), (0.0, 1.0, 0.0), 0),
+        ((0.0, 0.0, -0.25), (0.0, 1.0, 1.0), 2),
+        ((0.0, 0.0, -0.25), (0.0, 1.0, 0.0), 4),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz), count) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz).normalize());
+        assert_eq!(cone.local_intersect(&ray).len(), count);
+    }
+}
+
+#[test]
+fn computing_the_normal_vector_on_a_cone() {
+    let cone = Cone::default();
+    let cases = [
+        ((0.0, 0.0, 0.0), (0.0, 0.0, 0.0)),
+        ((1.0, 1.0, 1.0), (1.0, -2.0f64.sqrt(), 1.0)),
+        ((-1.0, -1.0, 0.0), (-1.0, 1.0, 0.0)),
+    ];
+    for ((px, py, pz), (nx, ny, nz)) in cases {
+        assert_eq!(
+            cone.local_normal_at(&Point::new(px, py, pz), None),
+            Vec4::new(nx, ny, nz)
+        );
+    }
+}
+
+#[test]
+fn the_normal_vector_on_a_cones_end_caps() {
+    let cone = Cone {
+        minimum: -1.0,
+        maximum: 1.0,
+        closed: true,
+        ..Cone::default()
+    };
+    assert_eq!(
+        cone.local_normal_at(&Point::new(0.5, 1.0, 0.0), None),
+        Vec4::new(0.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        cone.local_normal_at(&Point::new(0.0, -1.0, 0.5), None),
+        Vec4::new(0.0, -1.0, 0.0)
+    );
+}
@@ -0,0 +1,173 @@
+use crate::{
+    matrix::Matrix,
+    matters::{
+        csg::{Csg, CsgOperation},
+        cube::Cube,
+        group::Group,
+        sphere::Sphere,
+        Intersection, Shape,
+    },
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+#[test]
+fn csg_is_created_with_an_operation_and_two_shapes() {
+    let csg = Csg::union(Box::new(Sphere::default()), Box::new(Cube::default()));
+    assert_eq!(csg.operation, CsgOperation::Union);
+    assert_eq!(csg.transformation, Matrix::identity_4x4());
+    assert!(!std::ptr::addr_eq(csg.left(), csg.right()));
+}
+
+#[test]
+fn evaluating_the_rule_for_a_csg_operation() {
+    // (lhit, inl, inr, union, intersection, difference)
+    let cases = [
+        (true, true, true, false, true, false),
+        (true, true, false, true, false, true),
+        (true, false, true, false, true, false),
+        (true, false, false, true, false, true),
+        (false, true, true, false, true, true),
+        (false, true, false, false, true, true),
+        (false, false, true, true, false, false),
+        (false, false, false, true, false, false),
+    ];
+    for (lhit, inl, inr, union, intersection, difference) in cases {
+        assert_eq!(
+            CsgOperation::Union.intersection_allowed(lhit, inl, inr),
+            union
+        );
+        assert_eq!(
+            CsgOperation::Intersection.intersection_allowed(lhit, inl, inr),
+            intersection
+        );
+        assert_eq!(
+            CsgOperation::Difference.intersection_allowed(lhit, inl, inr),
+            difference
+        );
+    }
+}
+
+#[test]
+fn filtering_a_list_of_intersections() {
+    let cases = [
+        (CsgOperation::Union, 0, 3),
+        (CsgOperation::Intersection, 1, 2),
+        (CsgOperation::Difference, 0, 1),
+    ];
+    for (operation, x0, x1) in cases {
+        let csg = Csg::combine(
+            operation,
+            Box::new(Sphere::default()),
+            Box::new(Cube::default()),
+        );
+        let xs = vec![
+            Intersection::new(1.0, csg.left()),
+            Intersection::new(2.0, csg.right()),
+            Intersection::new(3.0, csg.left()),
+            Intersection::new(4.0, csg.right()),
+        ];
+        let result = csg.filter_intersections(xs.clone());
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].distance, xs[x0].distance);
+        assert_eq!(result[1].distance, xs[x1].distance);
+    }
+}
+
+#[test]
+fn a_ray_misses_a_csg_object() {
+    let csg = Csg::union(Box::new(Sphere::default()), Box::new(Cube::default()));
+    let ray = Ray::new(Point::new(0.0, 2.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert!(csg.local_intersect(&ray).is_empty());
+}
+
+#[test]
+fn a_ray_hits_a_csg_object() {
+    let csg = Csg::union(
+        Box::new(Sphere::default()),
+        Box::new(Sphere::new(Matrix::translation_mat_4x4(0.0, 0.0, 0.5))),
+    );
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = csg.local_intersect(&ray);
+    assert_eq!(
+        xs.iter().map(|i| i.distance).collect::<Vec<f64>>(),
+        vec![4.0, 6.5]
+    );
+    assert!(std::ptr::addr_eq(xs[0].object, csg.left()));
+    assert!(std::ptr::addr_eq(xs[1].object, csg.right()));
+}
+
+#[test]
+fn a_hole_carved_in_a_cube() {
+    // a thin cylinder like hole along z, made from a long narrow cube
+    let hole = Cube::new(Matrix::scaling_mat_4x4(0.5, 0.5, 2.0));
+    let csg = Csg::difference(Box::new(Cube::default()), Box::new(hole));
+    let through_hole = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert!(csg.local_intersect(&through_hole).is_empty());
+    let beside_hole = Ray::new(Point::new(0.75, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(
+        csg.local_intersect(&beside_hole)
+            .iter()
+            .map(|i| i.distance)
+            .collect::<Vec<f64>>(),
+        vec![4.0, 6.0]
+    );
+    // across the hole the ray leaves the cube at the hole walls
+    let across_hole = Ray::new(Point::new(-5.0, 0.0, 0.0), Vec4::new(1.0, 0.0, 0.0));
+    assert_eq!(
+        csg.local_intersect(&across_hole)
+            .iter()
+            .map(|i| i.distance)
+            .collect::<Vec<f64>>(),
+        vec![4.0, 4.5, 5.5, 6.0]
+    );
+}
+
+#[test]
+fn a_lens_from_the_intersection_of_two_spheres() {
+    let csg = Csg::intersection(
+        Box::new(Sphere::new(Matrix::translation_mat_4x4(0.0, 0.0, -0.5))),
+        Box::new(Sphere::new(Matrix::translation_mat_4x4(0.0, 0.0, 0.5))),
+    );
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = csg.local_intersect(&ray);
+    assert_eq!(
+        xs.iter().map(|i| i.distance).collect::<Vec<f64>>(),
+        vec![4.5, 5.5]
+    );
+    // the lens starts on the right sphere and ends on the left one
+    assert!(std::ptr::addr_eq(xs[0].object, csg.right()));
+    assert!(std::ptr::addr_eq(xs[1].object, csg.left()));
+}
+
+#[test]
+fn a_csg_with_a_group_child_and_its_normals() {
+    let mut group = Group::default();
+    group.add_child(Box::new(Sphere::default()));
+    let mut csg = Csg::difference(
+        Box::new(group),
+        Box::new(Sphere::new(Matrix::translation_mat_4x4(0.0, 0.0, -1.0))),
+    );
+    csg.set_transformation(Matrix::translation_mat_4x4(0.0, 0.0, 1.0));
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = csg.intersect(&ray);
+    // the front of the sphere in the group is carved away, the ray enters on the inside of the carving sphere
+    assert_eq!(
+        xs.iter().map(|i| i.distance).collect::<Vec<f64>>(),
+        vec![6.0, 7.0]
+    );
+    let hit = &xs[0];
+    assert_eq!(hit.parents.len(), 1);
+    let point = ray.position(hit.distance);
+    assert_eq!(
+        hit.object.normal_at(&point, Some(hit)),
+        Vec4::new(0.0, 0.0, 1.0)
+    );
+    let hit = &xs[1];
+    assert_eq!(hit.parents.len(), 2);
+    let point = ray.position(hit.distance);
+    assert_eq!(
+        hit.object.normal_at(&point, Some(hit)),
+        Vec4::new(0.0, 0.0, 1.0)
+    );
+}
@@ -0,0 +1,86 @@
+use crate::{
+    matrix::Matrix,
+    matters::{cube::Cube, material::Material, Shape},
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+#[test]
+fn default_cube_has_identity_transformation_and_default_material() {
+    let cube = Cube::default();
+    assert_eq!(cube.transformation, Matrix::identity_4x4());
+    assert_eq!(cube.material, Material::default());
+}
+
+#[test]
+fn a_ray_intersects_a_cube() {
+    let cube = Cube::default();
+    let cases = [
+        // +x, -x, +y, -y, +z, -z faces and a ray starting inside
+        ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), 4.0, 6.0),
+        ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), 4.0, 6.0),
+        ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), 4.0, 6.0),
+        ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), 4.0, 6.0),
+        ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), 4.0, 6.0),
+        ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+        ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), -1.0, 1.0),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz), t1, t2) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz));
+        let xs = cube.local_intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_eq!((xs[0].distance, xs[1].distance), (t1, t2));
+    }
+}
+
+#[test]
+fn a_ray_misses_a_cube() {
+    let cube = Cube::default();
+    let cases = [
+        ((-2.0, 0.0, 0.0), (0.2673, 0.5345, 0.8018)),
+        ((0.0, -2.0, 0.0), (0.8018, 0.2673, 0.5345)),
+        ((0.0, 0.0, -2.0), (0.5345, 0.8018, 0.2673)),
+        ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0)),
+        ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0)),
+        ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0)),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz)) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz));
+        assert!(cube.local_intersect(&ray).is_empty());
+    }
+}
+
+#[test]
+fn the_normal_on_the_surface_of_a_cube() {
+    let cube = Cube::default();
+    let cases = [
+        ((1.0, 0.5, -0.8), (1.0, 0.0, 0.0)),
+        ((-1.0, -0.2, 0.9), (-1.0, 0.0, 0.0)),
+        ((-0.4, 1.0, -0.1), (0.0, 1.0, 0.0)),
+        ((0.3, -1.0, -0.7), (0.0, -1.0, 0.0)),
+        ((-0.6, 0.3, 1.0), (0.0, 0.0, 1.0)),
+        ((0.4, 0.4, -1.0), (0.0, 0.0, -1.0)),
+        // corners pick the x axis
+        ((1.0, 1.0, 1.0), (1.0, 0.0, 0.0)),
+        ((-1.0, -1.0, -1.0), (-1.0, 0.0, 0.0)),
+    ];
+    for ((px, py, pz), (nx, ny, nz)) in cases {
+        assert_eq!(
+            cube.local_normal_at(&Point::new(px, py, pz), None),
+            Vec4::new(nx, ny, nz)
+        );
+    }
+}
+
+#[test]
+fn intersecting_a_transformed_cube() {
+    let cube =
+        Cube::new(Matrix::scaling_mat_4x4(2.0, 1.0, 1.0).translation_mat_4x4_chain(0.0, 0.0, 3.0));
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = cube.intersect(&ray);
+    assert_eq!((xs[0].distance, xs[1].distance), (7.0, 9.0));
+    assert_eq!(
+        cube.normal_at(&Point::new(2.0, 0.0, 3.0), None),
+        Vec4::new(1.0, 0.0, 0.0)
+    );
+}
@@ -0,0 +1,149 @@
+use crate::{
+    canvas::Color,
+    matrix::Matrix,
+    matters::{cylinder::Cylinder, light::Light, plane::Plane, Shape},
+    ray::Ray,
+    vector::{Point, Vec4},
+    world::World,
+};
+
+#[test]
+fn default_cylinder_is_infinite_and_open() {
+    let cylinder = Cylinder::default();
+    assert_eq!(cylinder.minimum, f64::NEG_INFINITY);
+    assert_eq!(cylinder.maximum, f64::INFINITY);
+    assert!(!cylinder.closed);
+}
+
+#[test]
+fn a_ray_misses_a_cylinder() {
+    let cylinder = Cylinder::default();
+    let cases = [
+        ((1.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+        ((0.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+        ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0)),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz)) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz).normalize());
+        assert!(cylinder.local_intersect(&ray).is_empty());
+    }
+}
+
+#[test]
+fn a_ray_strikes_a_cylinder() {
+    let cylinder = Cylinder::default();
+    let cases = [
+        ((1.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+        ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+        ((0.5, 0.0, -5.0), (0.1, 1.0, 1.0), 6.80798, 7.08872),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz), t0, t1) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz).normalize());
+        let xs = cylinder.local_intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert!((xs[0].distance - t0).abs() < 0.00001);
+        assert!((xs[1].distance - t1).abs() < 0.00001);
+    }
+}
+
+#[test]
+fn normal_vector_on_a_cylinder() {
+    let cylinder = Cylinder::default();
+    let cases = [
+        ((1.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+        ((0.0, 5.0, -1.0), (0.0, 0.0, -1.0)),
+        ((0.0, -2.0, 1.0), (0.0, 0.0, 1.0)),
+        ((-1.0, 1.0, 0.0), (-1.0, 0.0, 0.0)),
+    ];
+    for ((px, py, pz), (nx, ny, nz)) in cases {
+        assert_eq!(
+            cylinder.local_normal_at(&Point::new(px, py, pz), None),
+            Vec4::new(nx, ny, nz)
+        );
+    }
+}
+
+#[test]
+fn intersecting_a_constrained_cylinder() {
+    let cylinder = Cylinder {
+        minimum: 1.0,
+        maximum: 2.0,
+        ..Cylinder::default()
+    };
+    let cases = [
+        ((0.0, 1.5, 0.0), (0.1, 1.0, 0.0), 0),
+        ((0.0, 3.0, -5.0), (0.0, 0.0, 1.0), 0),
+        ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 0),
+        ((0.0, 2.0, -5.0), (0.0, 0.0, 1.0), 0),
+        ((0.0, 1.0, -5.0), (0.0, 0.0, 1.0), 0),
+        ((0.0, 1.5, -2.0), (0.0, 0.0, 1.0), 2),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz), count) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz).normalize());
+        assert_eq!(cylinder.local_intersect(&ray).len(), count);
+    }
+}
+
+#[test]
+fn intersecting_the_caps_of_a_closed_cylinder() {
+    let cylinder = Cylinder {
+        minimum: 1.0,
+        maximum: 2.0,
+        closed: true,
+        ..Cylinder::default()
+    };
+    let cases = [
+        ((0.0, 3.0, 0.0), (0.0, -1.0, 0.0), 2),
+        ((0.0, 3.0, -2.0), (0.0, -1.0, 2.0), 2),
+        // corner case
+        ((0.0, 4.0, -2.0), (0.0, -1.0, 1.0), 2),
+        ((0.0, 0.0, -2.0), (0.0, 1.0, 2.0), 2),
+        // corner case
+        ((0.0, -1.0, -2.0), (0.0, 1.0, 1.0), 2),
+    ];
+    for ((ox, oy, oz), (dx, dy, dz), count) in cases {
+        let ray = Ray::new(Point::new(ox, oy, oz), Vec4::new(dx, dy, dz).normalize());
+        assert_eq!(cylinder.local_intersect(&ray).len(), count);
+    }
+}
+
+#[test]
+fn the_normal_vector_on_a_cylinders_end_caps() {
+    let cylinder = Cylinder {
+        minimum: 1.0,
+        maximum: 2.0,
+        closed: true,
+        ..Cylinder::default()
+    };
+    let cases = [
+        ((0.0, 1.0, 0.0), (0.0, -1.0, 0.0)),
+        ((0.5, 1.0, 0.0), (0.0, -1.0, 0.0)),
+        ((0.0, 1.0, 0.5), (0.0, -1.0, 0.0)),
+        ((0.0, 2.0, 0.0), (0.0, 1.0, 0.0)),
+        ((0.5, 2.0, 0.0), (0.0, 1.0, 0.0)),
+        ((0.0, 2.0, 0.5), (0.0, 1.0, 0.0)),
+    ];
+    for ((px, py, pz), (nx, ny, nz)) in cases {
+        assert_eq!(
+            cylinder.local_normal_at(&Point::new(px, py, pz), None),
+            Vec4::new(nx, ny, nz)
+        );
+    }
+}
+
+#[test]
+fn a_closed_cylinder_casts_a_shadow() {
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(0.0, 10.0, 0.0), Color::white())];
+    let cylinder = Cylinder {
+        transformation: Matrix::translation_mat_4x4(0.0, 2.0, 0.0),
+        minimum: 0.0,
+        maximum: 1.0,
+        closed: true,
+        ..Cylinder::default()
+    };
+    world.set_objects(vec![Box::new(cylinder), Box::new(Plane::default())]);
+    assert!(world.is_shadowed(&world.lights[0], &Point::new(0.0, 0.00001, 0.0)));
+    assert!(world.is_shadowed(&world.lights[0], &Point::new(0.5, 0.00001, 0.5)));
+    assert!(!world.is_shadowed(&world.lights[0], &Point::new(2.0, 0.00001, 0.0)));
+}
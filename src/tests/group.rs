@@ -0,0 +1,126 @@
+use crate::{
+    matrix::Matrix,
+    matters::{group::Group, sphere::Sphere, Intersection, Shape},
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+#[test]
+fn creating_a_new_group() {
+    let group = Group::default();
+    assert_eq!(group.transformation, Matrix::identity_4x4());
+    assert!(group.is_empty());
+}
+
+#[test]
+fn intersecting_a_ray_with_an_empty_group() {
+    let group = Group::default();
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, 0.0, 1.0));
+    assert!(group.local_intersect(&ray).is_empty());
+}
+
+#[test]
+fn intersecting_a_ray_with_a_nonempty_group() {
+    let mut group = Group::default();
+    group.add_child(Box::new(Sphere::default()));
+    group.add_child(Box::new(Sphere::new(Matrix::translation_mat_4x4(
+        0.0, 0.0, -3.0,
+    ))));
+    group.add_child(Box::new(Sphere::new(Matrix::translation_mat_4x4(
+        5.0, 0.0, 0.0,
+    ))));
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = group.local_intersect(&ray);
+    assert_eq!(
+        xs.iter().map(|i| i.distance).collect::<Vec<f64>>(),
+        vec![1.0, 3.0, 4.0, 6.0]
+    );
+    assert!(std::ptr::addr_eq(
+        xs[0].object,
+        group.children()[1].as_ref()
+    ));
+    assert!(std::ptr::addr_eq(
+        xs[2].object,
+        group.children()[0].as_ref()
+    ));
+    // every intersection knows it came from the group
+    assert!(xs
+        .iter()
+        .all(|i| i.parents.len() == 1 && std::ptr::addr_eq(i.parents[0], &group)));
+}
+
+#[test]
+fn intersecting_a_transformed_group() {
+    let mut group = Group::new(Matrix::scaling_mat_4x4(2.0, 2.0, 2.0));
+    group.add_child(Box::new(Sphere::new(Matrix::translation_mat_4x4(
+        5.0, 0.0, 0.0,
+    ))));
+    let ray = Ray::new(Point::new(10.0, 0.0, -10.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(group.intersect(&ray).len(), 2);
+}
+
+// a sphere translated by (5, 0, 0) inside a group scaled by 2 inside a group rotated by 90 deg on y
+fn nested_groups() -> Group {
+    let mut g2 = Group::new(Matrix::scaling_mat_4x4(1.0, 2.0, 3.0));
+    g2.add_child(Box::new(Sphere::new(Matrix::translation_mat_4x4(
+        5.0, 0.0, 0.0,
+    ))));
+    let mut g1 = Group::new(Matrix::rotation_y_mat_4x4(std::f64::consts::FRAC_PI_2));
+    g1.add_child(Box::new(g2));
+    g1
+}
+
+#[test]
+fn converting_a_point_from_world_to_object_space() {
+    let g1 = Group::new(Matrix::rotation_y_mat_4x4(std::f64::consts::FRAC_PI_2));
+    let g2 = Group::new(Matrix::scaling_mat_4x4(2.0, 2.0, 2.0));
+    let sphere = Sphere::new(Matrix::translation_mat_4x4(5.0, 0.0, 0.0));
+    let point = sphere.world_to_object(&Point::new(-2.0, 0.0, -10.0), &[&g2, &g1], 0.0);
+    assert_eq!(point, Point::new(0.0, 0.0, -1.0));
+}
+
+#[test]
+fn converting_a_normal_from_object_to_world_space() {
+    let g1 = Group::new(Matrix::rotation_y_mat_4x4(std::f64::consts::FRAC_PI_2));
+    let g2 = Group::new(Matrix::scaling_mat_4x4(1.0, 2.0, 3.0));
+    let sphere = Sphere::new(Matrix::translation_mat_4x4(5.0, 0.0, 0.0));
+    let sqrt3_by_3 = 3.0f64.sqrt() / 3.0;
+    let normal = sphere.normal_to_world(
+        Vec4::new(sqrt3_by_3, sqrt3_by_3, sqrt3_by_3),
+        &[&g2, &g1],
+        0.0,
+    );
+    assert_eq!(normal, Vec4::new(0.28571, 0.42857, -0.85714));
+}
+
+#[test]
+fn finding_the_normal_on_a_child_object() {
+    let g1 = Group::new(Matrix::rotation_y_mat_4x4(std::f64::consts::FRAC_PI_2));
+    let g2 = Group::new(Matrix::scaling_mat_4x4(1.0, 2.0, 3.0));
+    let sphere = Sphere::new(Matrix::translation_mat_4x4(5.0, 0.0, 0.0));
+    let mut hit = Intersection::new(1.0, &sphere);
+    hit.parents = vec![&g2, &g1];
+    assert_eq!(
+        sphere.normal_at(&Point::new(1.7321, 1.1547, -5.5774), Some(&hit)),
+        Vec4::new(0.2857, 0.42854, -0.85716)
+    );
+}
+
+#[test]
+fn the_hit_on_an_object_in_nested_groups_walks_the_parent_chain() {
+    // the sphere ends up centered at (0, 0, -5) with radius 3 on x, 2 on y and 1 on z
+    let g1 = nested_groups();
+    let ray = Ray::new(Point::new(0.0, 0.0, -10.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = g1.intersect(&ray);
+    assert_eq!(
+        xs.iter().map(|i| i.distance).collect::<Vec<f64>>(),
+        vec![4.0, 6.0]
+    );
+    let hit = Intersection::hits(&xs).unwrap();
+    assert_eq!(hit.parents.len(), 2);
+    assert!(std::ptr::addr_eq(hit.parents[0], g1.children()[0].as_ref()));
+    assert!(std::ptr::addr_eq(hit.parents[1], &g1));
+    let comps = Intersection::prepare_computation(&hit, &ray, &xs);
+    assert_eq!(comps.point, Point::new(0.0, 0.0, -6.0));
+    assert_eq!(comps.normalv, Vec4::new(0.0, 0.0, -1.0));
+}
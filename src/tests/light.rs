@@ -1,4 +1,8 @@
-use crate::{canvas::Color, matters::light::Light, vector::Point};
+use crate::{
+    canvas::Color,
+    matters::light::{Falloff, Light, LightKind},
+    vector::{Point, Vec4},
+};
 
 #[test]
 fn a_point_light_has_a_position_and_intensity() {
@@ -7,4 +11,183 @@ fn a_point_light_has_a_position_and_intensity() {
     let light = Light::new(position.clone(), color.clone());
     assert_eq!(light.position, position);
     assert_eq!(light.intensity, color);
+    assert_eq!(light.samples(&Point::new(1.0, 2.0, 3.0)), vec![position]);
+}
+
+#[test]
+fn creating_an_area_light() {
+    let light = Light::area(
+        Point::origin(),
+        Vec4::new(2.0, 0.0, 0.0),
+        4,
+        Vec4::new(0.0, 0.0, 1.0),
+        2,
+        Color::white(),
+    );
+    assert_eq!(light.position, Point::new(1.0, 0.0, 0.5));
+    match light.kind {
+        LightKind::Area {
+            corner,
+            uvec,
+            usteps,
+            vvec,
+            vsteps,
+            jitter,
+        } => {
+            assert_eq!(corner, Point::origin());
+            assert_eq!((uvec, usteps), (Vec4::new(0.5, 0.0, 0.0), 4));
+            assert_eq!((vvec, vsteps), (Vec4::new(0.0, 0.0, 0.5), 2));
+            assert!(jitter);
+        }
+        _ => panic!("expected an area light"),
+    }
+}
+
+#[test]
+fn samples_of_an_area_light_without_jitter_are_the_cell_centers() {
+    let light = Light::area(
+        Point::origin(),
+        Vec4::new(2.0, 0.0, 0.0),
+        4,
+        Vec4::new(0.0, 0.0, 1.0),
+        2,
+        Color::white(),
+    )
+    .with_jitter(false);
+    let samples = light.samples(&Point::origin());
+    assert_eq!(samples.len(), 8);
+    assert_eq!(samples[0], Point::new(0.25, 0.0, 0.25));
+    assert_eq!(samples[1], Point::new(0.75, 0.0, 0.25));
+    assert_eq!(samples[2], Point::new(1.25, 0.0, 0.25));
+    assert_eq!(samples[3], Point::new(1.75, 0.0, 0.25));
+    assert_eq!(samples[7], Point::new(1.75, 0.0, 0.75));
+}
+
+#[test]
+fn jittered_samples_stay_in_their_cells_and_repeat_for_the_same_point() {
+    let light = Light::area(
+        Point::origin(),
+        Vec4::new(2.0, 0.0, 0.0),
+        4,
+        Vec4::new(0.0, 0.0, 1.0),
+        2,
+        Color::white(),
+    );
+    let point = Point::new(3.0, 4.0, 5.0);
+    let samples = light.samples(&point);
+    for (index, sample) in samples.iter().enumerate() {
+        let (u, v) = ((index % 4) as f64, (index / 4) as f64);
+        assert!(sample.0 >= u * 0.5 && sample.0 < (u + 1.0) * 0.5);
+        assert!(sample.2 >= v * 0.5 && sample.2 < (v + 1.0) * 0.5);
+        assert_eq!(sample.1, 0.0);
+    }
+    assert_eq!(light.samples(&point), samples);
+    assert_ne!(light.samples(&Point::new(3.0, 4.0, 5.5)), samples);
+    assert_ne!(samples[0], Point::new(0.25, 0.0, 0.25));
+}
+
+#[test]
+fn creating_a_spot_light() {
+    let light = Light::spot(
+        Point::new(0.0, 5.0, 0.0),
+        Vec4::new(0.0, -2.0, 0.0),
+        0.5,
+        0.25,
+        Color::white(),
+    );
+    match light.kind {
+        LightKind::Spot {
+            direction,
+            inner_angle,
+            outer_angle,
+        } => {
+            assert_eq!(direction, Vec4::new(0.0, -1.0, 0.0));
+            assert_eq!((inner_angle, outer_angle), (0.5, 0.5));
+        }
+        _ => panic!("expected a spot light"),
+    }
+}
+
+#[test]
+fn a_spot_light_fades_smoothly_between_its_cones() {
+    let light = Light::spot(
+        Point::origin(),
+        Vec4::new(0.0, 0.0, 1.0),
+        std::f64::consts::FRAC_PI_6,
+        std::f64::consts::FRAC_PI_3,
+        Color::white(),
+    );
+    // light_vector goes from the lit point to the light, so it's opposite to the spot direction in the middle of the cone
+    let towards_light = |angle: f64| Vec4::new(-angle.sin(), 0.0, -angle.cos());
+    assert_eq!(light.cone_factor(&towards_light(0.0)), 1.0);
+    assert_eq!(light.cone_factor(&towards_light(0.5)), 1.0);
+    assert_eq!(light.cone_factor(&towards_light(1.1)), 0.0);
+    assert_eq!(light.cone_factor(&towards_light(std::f64::consts::PI)), 0.0);
+    let middle =
+        ((std::f64::consts::FRAC_PI_6.cos() + std::f64::consts::FRAC_PI_3.cos()) / 2.0).acos();
+    assert!((light.cone_factor(&towards_light(middle)) - 0.5).abs() < 0.00001);
+    let factor = light.cone_factor(&towards_light(0.7));
+    assert!(factor > 0.5 && factor < 1.0);
+    // other lights shine everywhere
+    assert_eq!(
+        Light::new(Point::origin(), Color::white()).cone_factor(&towards_light(2.0)),
+        1.0
+    );
+}
+
+#[test]
+fn a_directional_light_comes_from_the_same_direction_everywhere() {
+    let light = Light::directional(Vec4::new(0.0, -3.0, 0.0), Color::white());
+    for point in [Point::origin(), Point::new(10.0, -4.0, 2.0)] {
+        assert!(light.samples(&point).is_empty());
+        assert_eq!(
+            light.light_vectors(&point),
+            vec![(Vec4::new(0.0, 1.0, 0.0), f64::INFINITY)]
+        );
+    }
+}
+
+#[test]
+fn lights_do_not_fall_off_by_default() {
+    let light = Light::new(Point::origin(), Color::white());
+    assert_eq!(light.falloff, Falloff::None);
+    assert_eq!(light.attenuation(1000.0), 1.0);
+}
+
+#[test]
+fn falloff_starts_at_the_radius() {
+    let linear = Falloff::Linear { radius: 2.0 };
+    let inverse_square = Falloff::InverseSquare { radius: 2.0 };
+    for (distance, linear_result, inverse_square_result) in [
+        (0.0, 1.0, 1.0),
+        (1.0, 1.0, 1.0),
+        (2.0, 1.0, 1.0),
+        (4.0, 0.5, 0.25),
+        (8.0, 0.25, 0.0625),
+    ] {
+        assert_eq!(linear.attenuation(distance), linear_result);
+        assert_eq!(inverse_square.attenuation(distance), inverse_square_result);
+    }
+    // a directional light is infinitely far away, it would have nothing left
+    let sun =
+        Light::directional(Vec4::new(0.0, -1.0, 0.0), Color::white()).with_falloff(inverse_square);
+    assert_eq!(sun.attenuation(f64::INFINITY), 1.0);
+}
+
+#[test]
+fn the_intensity_of_a_light_can_be_given_by_it_power() {
+    let light = Light::new(Point::origin(), Color::new(1.0, 0.5, 0.0))
+        .with_power(4.0 * std::f64::consts::PI * 100.0);
+    assert_eq!(light.falloff, Falloff::InverseSquare { radius: 1.0 });
+    assert_eq!(light.intensity, Color::new(100.0, 50.0, 0.0));
+    // the radius it already had is kept
+    let light = Light::new(Point::origin(), Color::white())
+        .with_falloff(Falloff::InverseSquare { radius: 2.0 })
+        .with_power(4.0 * std::f64::consts::PI * 100.0);
+    assert_eq!(light.falloff, Falloff::InverseSquare { radius: 2.0 });
+    assert_eq!(light.intensity, Color::new(25.0, 25.0, 25.0));
+    // a directional light gets all of it everywhere
+    let sun = Light::directional(Vec4::new(0.0, -1.0, 0.0), Color::white()).with_power(3.0);
+    assert_eq!(sun.falloff, Falloff::None);
+    assert_eq!(sun.intensity, Color::new(3.0, 3.0, 3.0));
 }
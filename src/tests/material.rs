@@ -1,6 +1,10 @@
 use crate::{
     canvas::Color,
-    matters::{light::Light, material::Material},
+    matters::{
+        light::{Falloff, Light},
+        material::Material,
+        sphere::Sphere,
+    },
     vector::{Point, Vec4},
 };
 
@@ -12,6 +16,9 @@ fn default_material() {
     assert_eq!(material.diffuse, 0.9);
     assert_eq!(material.specular, 0.9);
     assert_eq!(material.shininess, 200.0);
+    assert_eq!(material.reflective, 0.0);
+    assert_eq!(material.transparency, 0.0);
+    assert_eq!(material.refractive_index, 1.0);
 }
 
 #[test]
@@ -19,14 +26,17 @@ fn lighting_with_eye_between_the_light_and_surface() {
     let eye_vector = Vec4::new(0.0, 0.0, -1.0);
     let normal_vector = Vec4::new(0.0, 0.0, -1.0);
     let light = Light::new(Point::new(0.0, 0.0, -10.0), Color::white());
-    let mut material = Material::default();
+    let material = Material::default();
     assert_eq!(
         material.lighting(
+            &Sphere::default(),
+            &[],
+            0.0,
             &light,
             &Point::new(0.0, 0.0, 0.0),
             &eye_vector,
             &normal_vector,
-            false,
+            1.0,
         ),
         Color::new(1.9, 1.9, 1.9)
     );
@@ -37,14 +47,17 @@ fn lighting_with_eye_between_the_light_and_surface_with_eye_offset_45_deg() {
     let eye_vector = Vec4::new(0.0, 2.0f64.sqrt() / 2.0, -2.0f64.sqrt() / 2.0);
     let normal_vector = Vec4::new(0.0, 0.0, -1.0);
     let light = Light::new(Point::new(0.0, 0.0, -10.0), Color::white());
-    let mut material = Material::default();
+    let material = Material::default();
     assert_eq!(
         material.lighting(
+            &Sphere::default(),
+            &[],
+            0.0,
             &light,
             &Point::new(0.0, 0.0, 0.0),
             &eye_vector,
             &normal_vector,
-            false,
+            1.0,
         ),
         Color::new(1.0, 1.0, 1.0)
     );
@@ -55,14 +68,17 @@ fn lighting_with_eye_opposite_surface_with_light_offset_45_deg() {
     let eye_vector = Vec4::new(0.0, 0.0, -1.0);
     let normal_vector = Vec4::new(0.0, 0.0, -1.0);
     let light = Light::new(Point::new(0.0, 10.0, -10.0), Color::white());
-    let mut material = Material::default();
+    let material = Material::default();
     assert_eq!(
         material.lighting(
+            &Sphere::default(),
+            &[],
+            0.0,
             &light,
             &Point::new(0.0, 0.0, 0.0),
             &eye_vector,
             &normal_vector,
-            false,
+            1.0,
         ),
         Color::new(0.7364, 0.7364, 0.7364)
     );
@@ -73,14 +89,17 @@ fn lighting_with_eye_in_path_of_the_reflection_vector() {
     let eye_vector = Vec4::new(0.0, -2.0f64.sqrt() / 2.0, -2.0f64.sqrt() / 2.0);
     let normal_vector = Vec4::new(0.0, 0.0, -1.0);
     let light = Light::new(Point::new(0.0, 10.0, -10.0), Color::white());
-    let mut material = Material::default();
+    let material = Material::default();
     assert_eq!(
         material.lighting(
+            &Sphere::default(),
+            &[],
+            0.0,
             &light,
             &Point::new(0.0, 0.0, 0.0),
             &eye_vector,
             &normal_vector,
-            false,
+            1.0,
         ),
         Color::new(1.6364, 1.6364, 1.6364)
     );
@@ -91,14 +110,17 @@ fn lighting_with_light_behind_the_surface() {
     let eye_vector = Vec4::new(0.0, 0.0, -1.0);
     let normal_vector = Vec4::new(0.0, 0.0, -1.0);
     let light = Light::new(Point::new(0.0, 0.0, 10.0), Color::white());
-    let mut material = Material::default();
+    let material = Material::default();
     assert_eq!(
         material.lighting(
+            &Sphere::default(),
+            &[],
+            0.0,
             &light,
             &Point::new(0.0, 0.0, 0.0),
             &eye_vector,
             &normal_vector,
-            false,
+            1.0,
         ),
         Color::new(0.1, 0.1, 0.1)
     );
@@ -109,15 +131,123 @@ fn lighting_with_the_surface_in_shadow() {
     let eye_vector = Vec4::new(0.0, 0.0, -1.0);
     let normal_vector = Vec4::new(0.0, 0.0, -1.0);
     let light = Light::new(Point::new(0.0, 0.0, -10.0), Color::white());
-    let mut material = Material::default();
+    let material = Material::default();
     assert_eq!(
         material.lighting(
+            &Sphere::default(),
+            &[],
+            0.0,
             &light,
             &Point::new(0.0, 0.0, 0.0),
             &eye_vector,
             &normal_vector,
-            true
+            0.0
         ),
         Color::new(0.1, 0.1, 0.1)
     );
 }
+
+#[test]
+fn lighting_with_a_directional_light() {
+    let light = Light::directional(Vec4::new(0.0, 0.0, 1.0), Color::white());
+    let sphere = Sphere::default();
+    // like a point light straight behind the eye
+    assert_eq!(
+        sphere.material.lighting(
+            &sphere,
+            &[],
+            0.0,
+            &light,
+            &Point::new(0.0, 0.0, -1.0),
+            &Vec4::new(0.0, 0.0, -1.0),
+            &Vec4::new(0.0, 0.0, -1.0),
+            1.0,
+        ),
+        Color::new(1.9, 1.9, 1.9)
+    );
+}
+
+#[test]
+fn lighting_with_a_spot_light_inside_and_outside_the_cone() {
+    let sphere = Sphere::default();
+    let lit = |direction: Vec4| {
+        let light = Light::spot(
+            Point::new(0.0, 0.0, -10.0),
+            direction,
+            std::f64::consts::FRAC_PI_8,
+            std::f64::consts::FRAC_PI_4,
+            Color::white(),
+        );
+        sphere.material.lighting(
+            &sphere,
+            &[],
+            0.0,
+            &light,
+            &Point::new(0.0, 0.0, -1.0),
+            &Vec4::new(0.0, 0.0, -1.0),
+            &Vec4::new(0.0, 0.0, -1.0),
+            1.0,
+        )
+    };
+    assert_eq!(lit(Vec4::new(0.0, 0.0, 1.0)), Color::new(1.9, 1.9, 1.9));
+    // pointing away, only the ambient light is left
+    assert_eq!(lit(Vec4::new(0.0, 1.0, 0.0)), Color::new(0.1, 0.1, 0.1));
+}
+
+#[test]
+fn lighting_with_a_light_which_falls_off() {
+    let eye_vector = Vec4::new(0.0, 0.0, -1.0);
+    let normal_vector = Vec4::new(0.0, 0.0, -1.0);
+    let material = Material::default();
+    let lit = |falloff: Falloff| {
+        material.lighting(
+            &Sphere::default(),
+            &[],
+            0.0,
+            &Light::new(Point::new(0.0, 0.0, -10.0), Color::white()).with_falloff(falloff),
+            &Point::new(0.0, 0.0, 0.0),
+            &eye_vector,
+            &normal_vector,
+            1.0,
+        )
+    };
+    assert_eq!(lit(Falloff::None), Color::new(1.9, 1.9, 1.9));
+    assert_eq!(
+        lit(Falloff::Linear { radius: 5.0 }),
+        Color::new(0.95, 0.95, 0.95)
+    );
+    assert_eq!(
+        lit(Falloff::InverseSquare { radius: 5.0 }),
+        Color::new(0.475, 0.475, 0.475)
+    );
+    // the point is within the radius
+    assert_eq!(
+        lit(Falloff::InverseSquare { radius: 20.0 }),
+        Color::new(1.9, 1.9, 1.9)
+    );
+}
+
+#[test]
+fn lights_with_the_same_power_light_a_closer_point_more() {
+    let eye_vector = Vec4::new(0.0, 0.0, -1.0);
+    let normal_vector = Vec4::new(0.0, 0.0, -1.0);
+    let material = Material::default();
+    let lit = |distance: f64| {
+        material.lighting(
+            &Sphere::default(),
+            &[],
+            0.0,
+            &Light::new(Point::new(0.0, 0.0, -distance), Color::white()).with_power(1000.0),
+            &Point::new(0.0, 0.0, 0.0),
+            &eye_vector,
+            &normal_vector,
+            1.0,
+        )
+    };
+    // twice as far is a quarter of the light
+    assert_eq!(lit(2.0), 4.0 * lit(4.0));
+    assert_eq!(
+        lit(2.0),
+        (1.9 * 1000.0 / (4.0 * std::f64::consts::PI * 4.0)) * Color::white()
+    );
+}
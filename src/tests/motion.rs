@@ -0,0 +1,187 @@
+use crate::{
+    canvas::Color,
+    matrix::Matrix,
+    matters::{
+        camera::Camera,
+        light::Light,
+        motion::{Motion, Moving},
+        plane::Plane,
+        sphere::Sphere,
+        Intersection, Shape,
+    },
+    ray::Ray,
+    vector::{Point, Vec4},
+    world::World,
+};
+
+// a unit sphere going from the origin (at time 0) to x = 4 (at time 1)
+fn moving_sphere() -> Moving {
+    Moving::new(
+        Box::new(Sphere::default()),
+        Motion::linear(
+            Matrix::identity_4x4(),
+            Matrix::translation_mat_4x4(4.0, 0.0, 0.0),
+        ),
+    )
+}
+
+#[test]
+fn a_motion_blends_from_one_keyframe_to_the_next() {
+    let motion = Motion::linear(
+        Matrix::translation_mat_4x4(0.0, 0.0, 0.0),
+        Matrix::translation_mat_4x4(4.0, 2.0, 0.0),
+    );
+    assert_eq!(
+        motion.transformation_at(0.5),
+        Matrix::translation_mat_4x4(2.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        motion.transformation_at(0.25),
+        Matrix::translation_mat_4x4(1.0, 0.5, 0.0)
+    );
+    // it stays put before the first keyframe and after the last one
+    assert_eq!(motion.transformation_at(-1.0), Matrix::identity_4x4());
+    assert_eq!(
+        motion.transformation_at(3.0),
+        Matrix::translation_mat_4x4(4.0, 2.0, 0.0)
+    );
+}
+
+#[test]
+fn keyframes_are_sorted_by_time() {
+    let motion = Motion::keyframed(vec![
+        (2.0, Matrix::scaling_mat_4x4(3.0, 3.0, 3.0)),
+        (0.0, Matrix::identity_4x4()),
+        (1.0, Matrix::translation_mat_4x4(0.0, 1.0, 0.0)),
+    ]);
+    let times = motion
+        .keyframes()
+        .iter()
+        .map(|(time, _)| *time)
+        .collect::<Vec<f64>>();
+    assert_eq!(times, vec![0.0, 1.0, 2.0]);
+    assert_eq!(
+        motion.transformation_at(1.0),
+        Matrix::translation_mat_4x4(0.0, 1.0, 0.0)
+    );
+    // no keyframes at all does not move
+    assert_eq!(
+        Motion::keyframed(vec![]).transformation_at(5.0),
+        Matrix::identity_4x4()
+    );
+}
+
+#[test]
+fn a_moving_shape_is_hit_where_it_is_at_the_time_of_the_ray() {
+    let sphere = moving_sphere();
+    let ray = Ray::new(Point::new(4.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert!(sphere.intersect(&ray).is_empty());
+    let ray = ray.with_time(1.0);
+    let xs = sphere.intersect(&ray);
+    assert_eq!(xs.len(), 2);
+    assert_eq!((xs[0].distance, xs[1].distance), (4.0, 6.0));
+    // the hit is on the child, the moving shape is it's parent
+    assert!(std::ptr::addr_eq(xs[0].object, sphere.child()));
+    assert!(std::ptr::addr_eq(xs[0].parents[0], &sphere));
+    assert_eq!(xs[0].time, 1.0);
+}
+
+#[test]
+fn the_normal_of_a_moving_shape_is_where_it_was_hit() {
+    let sphere = moving_sphere();
+    let ray = Ray::new(Point::new(2.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0)).with_time(0.5);
+    let xs = sphere.intersect(&ray);
+    let comps = Intersection::prepare_computation(&xs[0], &ray, &xs);
+    assert_eq!(comps.point, Point::new(2.0, 0.0, -1.0));
+    assert_eq!(comps.normalv, Vec4::new(0.0, 0.0, -1.0));
+    assert_eq!(comps.time, 0.5);
+    // at time 0 the same point would be on the side of the sphere
+    let normal = xs[0].object.normal_at(
+        &Point::new(2.0, 0.0, -1.0),
+        Some(&Intersection {
+            time: 0.0,
+            ..xs[0].clone()
+        }),
+    );
+    assert_eq!(normal, Vec4::new(2.0, 0.0, -1.0).normalize());
+}
+
+#[test]
+fn the_bounds_of_a_moving_shape_cover_it_s_whole_path() {
+    let bounds = moving_sphere().parent_space_bounds();
+    assert_eq!(bounds.min, Point::new(-1.0, -1.0, -1.0));
+    assert_eq!(bounds.max, Point::new(5.0, 1.0, 1.0));
+}
+
+#[test]
+fn a_moving_shape_casts_it_s_shadow_where_it_is() {
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(4.0, 10.0, 0.0), Color::white())];
+    world.set_objects(vec![Box::new(moving_sphere())]);
+    world.build_bvh();
+    let point = Point::new(4.0, -5.0, 0.0);
+    let light = &world.lights[0];
+    assert_eq!(world.intensity_at_time(light, &point, 0.0), 1.0);
+    assert_eq!(world.intensity_at_time(light, &point, 1.0), 0.0);
+}
+
+#[test]
+fn shading_a_timed_ray_uses_the_shadow_at_that_time() {
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(4.0, 10.0, 0.0), Color::white())];
+    world.set_objects(vec![
+        Box::new(moving_sphere()),
+        Box::new(Plane::new(Matrix::translation_mat_4x4(0.0, -5.0, 0.0))),
+    ]);
+    // looking straight down at the floor below where the sphere ends up
+    let ray = Ray::new(Point::new(4.0, -4.5, 0.0), Vec4::new(0.0, -1.0, 0.0));
+    assert_eq!(world.color_at(&ray), Color::new(1.9, 1.9, 1.9));
+    assert_eq!(
+        world.color_at(&ray.with_time(1.0)),
+        Color::new(0.1, 0.1, 0.1)
+    );
+}
+
+#[test]
+fn moving_shapes_are_smeared_and_still_ones_stay_sharp() {
+    // white spheres on black, so a pixel's color is how long the sphere covered it while the shutter was open
+    let flat = |mut sphere: Sphere| {
+        sphere.material.ambient = 1.0;
+        sphere.material.diffuse = 0.0;
+        sphere.material.specular = 0.0;
+        sphere
+    };
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(0.0, 0.0, -10.0), Color::white())];
+    world.set_objects(vec![
+        Box::new(Moving::new(
+            Box::new(flat(Sphere::new(Matrix::scaling_mat_4x4(0.5, 0.5, 0.5)))),
+            Motion::linear(
+                Matrix::translation_mat_4x4(-3.0, 2.0, 0.0),
+                Matrix::translation_mat_4x4(3.0, 2.0, 0.0),
+            ),
+        )),
+        Box::new(flat(Sphere::new(
+            Matrix::scaling_mat_4x4(0.5, 0.5, 0.5).translation_mat_4x4_chain(0.0, -2.0, 0.0),
+        ))),
+    ]);
+    let mut camera = Camera::new(21, 21, std::f64::consts::FRAC_PI_2);
+    camera.transform = Matrix::view_transformation(
+        Point::new(0.0, 0.0, -5.0),
+        Point::origin(),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    camera.samples = 64;
+    camera.shutter_close = 1.0;
+    // the moving sphere is seen only part of the time on its path
+    let smeared = camera.color_for_pixel(&world, 10, 6);
+    assert!(smeared.red() > 0.0 && smeared.red() < 0.5);
+    assert_eq!(smeared.red(), smeared.green());
+    assert!(camera.color_for_pixel(&world, 4, 6).red() > 0.0);
+    // the one standing still is fully there
+    assert_eq!(camera.color_for_pixel(&world, 10, 14), Color::white());
+    // with the shutter closed at time 0 the moving sphere is at the start of its path
+    camera.shutter_close = 0.0;
+    assert_eq!(camera.color_for_pixel(&world, 10, 6), Color::black());
+    assert_eq!(camera.color_for_pixel(&world, 4, 6), Color::white());
+}
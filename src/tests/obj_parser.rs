@@ -0,0 +1,188 @@
+use crate::{
+    matters::{Intersection, Shape},
+    obj_parser::ObjParser,
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+#[test]
+fn ignoring_unrecognized_lines() {
+    let gibberish = "There was a young lady named Bright
+who traveled much faster than light.
+She set out one day
+in a relative way,
+and came back the previous night.";
+    let parser = ObjParser::parse(gibberish);
+    assert_eq!(parser.ignored_lines, 5);
+    assert!(parser.malformed_lines.is_empty());
+    assert!(parser.default_group.is_empty());
+}
+
+#[test]
+fn comments_and_blank_lines_are_not_counted() {
+    let parser = ObjParser::parse("# a comment\n\n   \nv 1 2 3\n");
+    assert_eq!(parser.ignored_lines, 0);
+    assert_eq!(parser.vertices.len(), 1);
+}
+
+#[test]
+fn vertex_records() {
+    let file = "v -1 1 0
+v -1.0000 0.5000 0.0000
+v 1 0 0
+v 1 1 0";
+    let parser = ObjParser::parse(file);
+    assert_eq!(
+        parser.vertices,
+        vec![
+            Point::new(-1.0, 1.0, 0.0),
+            Point::new(-1.0, 0.5, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn vertex_normal_and_texture_records() {
+    let file = "vn 0 0 1
+vn 0.707 0 -0.707
+vn 1 2 3
+vt 0.5 0.25
+vt 1";
+    let parser = ObjParser::parse(file);
+    assert_eq!(
+        parser.normals,
+        vec![
+            Vec4::new(0.0, 0.0, 1.0),
+            Vec4::new(0.707, 0.0, -0.707),
+            Vec4::new(1.0, 2.0, 3.0),
+        ]
+    );
+    assert_eq!(parser.texture_coords, vec![(0.5, 0.25), (1.0, 0.0)]);
+}
+
+#[test]
+fn parsing_triangle_faces() {
+    let file = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+f 1 2 3
+f 1 3 4";
+    let parser = ObjParser::parse(file);
+    assert_eq!(parser.default_group.children().len(), 2);
+    // the first triangle covers the lower left half and the second one the upper right half
+    let ray = Ray::new(Point::new(-0.5, 0.25, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(parser.default_group.children()[0].intersect(&ray).len(), 1);
+    assert!(parser.default_group.children()[1]
+        .intersect(&ray)
+        .is_empty());
+}
+
+#[test]
+fn triangulating_polygons() {
+    let file = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+v 0 2 0
+
+f 1 2 3 4 5";
+    let parser = ObjParser::parse(file);
+    assert_eq!(parser.default_group.children().len(), 3);
+    // the last triangle of the fan is (1, 4, 5)
+    let ray = Ray::new(Point::new(0.0, 1.5, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(parser.default_group.children()[2].intersect(&ray).len(), 1);
+}
+
+#[test]
+fn triangles_in_named_groups() {
+    let file = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4
+g FirstGroup
+f 1 3 4";
+    let parser = ObjParser::parse(file);
+    assert!(parser.default_group.is_empty());
+    assert_eq!(parser.groups.len(), 2);
+    assert_eq!(parser.groups[0].0, "FirstGroup");
+    assert_eq!(parser.groups[0].1.children().len(), 2);
+    assert_eq!(parser.groups[1].0, "SecondGroup");
+    assert_eq!(parser.groups[1].1.children().len(), 1);
+}
+
+#[test]
+fn converting_an_obj_file_to_a_group() {
+    let file = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+f 1 2 3
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4
+g Empty";
+    let group = ObjParser::parse(file).into_group();
+    // one triangle of it's own and the two named groups, empty groups are dropped
+    assert_eq!(group.children().len(), 3);
+    let ray = Ray::new(Point::new(-0.5, 0.25, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(group.intersect(&ray).len(), 2);
+}
+
+#[test]
+fn faces_with_normals_give_smooth_triangles() {
+    let file = "v 0 1 0
+v -1 0 0
+v 1 0 0
+
+vn -1 0 0
+vn 1 0 0
+vn 0 1 0
+
+f 1//3 2//1 3//2
+f 1/0/3 2/102/1 3/14/2
+vt 0 0
+f -3/1/3 -2/1/-3 -1/-1/2";
+    let parser = ObjParser::parse(file);
+    // the second face refers to texture coordinates which don't exist yet
+    assert_eq!(parser.malformed_lines, vec![10]);
+    assert_eq!(parser.default_group.children().len(), 2);
+    for triangle in parser.default_group.children() {
+        let hit = Intersection::with_uv(1.0, triangle.as_ref(), 0.45, 0.25);
+        assert_eq!(
+            triangle.normal_at(&Point::origin(), Some(&hit)),
+            Vec4::new(-0.5547, 0.83205, 0.0)
+        );
+    }
+}
+
+#[test]
+fn malformed_lines_are_reported_instead_of_panicking() {
+    let file = "v 1 2
+v 1 2 3
+v 1 2 x
+vn 1
+f 1 2
+f 1 1 7
+f 1/1/1/1 1 1
+g
+s off
+f 1 1 1";
+    let parser = ObjParser::parse(file);
+    assert_eq!(parser.malformed_lines, vec![1, 3, 4, 5, 6, 7]);
+    assert_eq!(parser.ignored_lines, 1);
+    assert_eq!(parser.vertices.len(), 1);
+    assert_eq!(parser.default_group.children().len(), 1);
+    assert_eq!(
+        parser.report(),
+        "1 vertices, 0 normals, 0 texture coordinates, 1 triangles, 0 groups, 1 ignored lines, 6 malformed lines [1, 3, 4, 5, 6, 7]"
+    );
+}
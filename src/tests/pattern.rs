@@ -0,0 +1,322 @@
+use std::sync::Arc;
+
+use crate::{
+    canvas::Color,
+    matrix::Matrix,
+    matters::{
+        group::Group,
+        light::Light,
+        material::Material,
+        pattern::{Blend, Checkers, Gradient, Pattern, Perturbed, Ring, Solid, Stripe},
+        perlin,
+        sphere::Sphere,
+        Shape,
+    },
+    ray::Ray,
+    vector::{Point, Vec4},
+    world::World,
+};
+
+// gives back the point it was looked up at as a color, to check the transformations
+#[derive(Debug)]
+struct TestPattern {
+    transformation: Matrix,
+}
+
+impl Pattern for TestPattern {
+    fn transformation(&self) -> &Matrix {
+        &self.transformation
+    }
+
+    fn set_transformation(&mut self, transformation_matrix: Matrix) {
+        self.transformation = transformation_matrix;
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        Color::new(point.0, point.1, point.2)
+    }
+}
+
+fn test_pattern() -> TestPattern {
+    TestPattern {
+        transformation: Matrix::identity_4x4(),
+    }
+}
+
+#[test]
+fn a_stripe_pattern_alternates_in_x_only() {
+    let pattern = Stripe::new(Color::white(), Color::black());
+    // the colors become solid patterns
+    assert_eq!(pattern.a.pattern_at(&Point::origin()), Color::white());
+    assert_eq!(pattern.b.pattern_at(&Point::origin()), Color::black());
+    for (point, color) in [
+        (Point::new(0.0, 1.0, 0.0), Color::white()),
+        (Point::new(0.0, 2.0, 2.0), Color::white()),
+        (Point::new(0.9, 0.0, 0.0), Color::white()),
+        (Point::new(1.0, 0.0, 0.0), Color::black()),
+        (Point::new(-0.1, 0.0, 0.0), Color::black()),
+        (Point::new(-1.0, 0.0, 0.0), Color::black()),
+        (Point::new(-1.1, 0.0, 0.0), Color::white()),
+    ] {
+        assert_eq!(pattern.pattern_at(&point), color);
+    }
+}
+
+#[test]
+fn a_pattern_with_an_object_transformation() {
+    let object = Sphere::new(Matrix::scaling_mat_4x4(2.0, 2.0, 2.0));
+    let pattern = test_pattern();
+    assert_eq!(
+        pattern.pattern_at_shape(&object, &[], 0.0, &Point::new(2.0, 3.0, 4.0)),
+        Color::new(1.0, 1.5, 2.0)
+    );
+}
+
+#[test]
+fn a_pattern_with_a_pattern_transformation() {
+    let object = Sphere::default();
+    let mut pattern = test_pattern();
+    pattern.set_transformation(Matrix::scaling_mat_4x4(2.0, 2.0, 2.0));
+    assert_eq!(
+        pattern.pattern_at_shape(&object, &[], 0.0, &Point::new(2.0, 3.0, 4.0)),
+        Color::new(1.0, 1.5, 2.0)
+    );
+}
+
+#[test]
+fn a_pattern_with_both_an_object_and_a_pattern_transformation() {
+    let object = Sphere::new(Matrix::scaling_mat_4x4(2.0, 2.0, 2.0));
+    let mut pattern = test_pattern();
+    pattern.set_transformation(Matrix::translation_mat_4x4(0.5, 1.0, 1.5));
+    assert_eq!(
+        pattern.pattern_at_shape(&object, &[], 0.0, &Point::new(2.5, 3.0, 3.5)),
+        Color::new(0.75, 0.5, 0.25)
+    );
+}
+
+#[test]
+fn a_gradient_linearly_interpolates_between_colors() {
+    let pattern = Gradient::new(Color::white(), Color::black());
+    for (x, color) in [
+        (0.0, Color::white()),
+        (0.25, Color::new(0.75, 0.75, 0.75)),
+        (0.5, Color::new(0.5, 0.5, 0.5)),
+        (0.75, Color::new(0.25, 0.25, 0.25)),
+    ] {
+        assert_eq!(pattern.pattern_at(&Point::new(x, 0.0, 0.0)), color);
+    }
+}
+
+#[test]
+fn a_ring_should_extend_in_both_x_and_z() {
+    let pattern = Ring::new(Color::white(), Color::black());
+    for (point, color) in [
+        (Point::new(0.0, 0.0, 0.0), Color::white()),
+        (Point::new(1.0, 0.0, 0.0), Color::black()),
+        (Point::new(0.0, 0.0, 1.0), Color::black()),
+        // 0.708 = just slightly more than sqrt(2)/2
+        (Point::new(0.708, 0.0, 0.708), Color::black()),
+    ] {
+        assert_eq!(pattern.pattern_at(&point), color);
+    }
+}
+
+#[test]
+fn checkers_should_repeat_in_every_dimension() {
+    let pattern = Checkers::new(Color::white(), Color::black());
+    for (point, color) in [
+        (Point::new(0.0, 0.0, 0.0), Color::white()),
+        (Point::new(0.99, 0.0, 0.0), Color::white()),
+        (Point::new(1.01, 0.0, 0.0), Color::black()),
+        (Point::new(0.0, 0.99, 0.0), Color::white()),
+        (Point::new(0.0, 1.01, 0.0), Color::black()),
+        (Point::new(0.0, 0.0, 0.99), Color::white()),
+        (Point::new(0.0, 0.0, 1.01), Color::black()),
+        (Point::new(-0.5, 0.0, 0.0), Color::black()),
+        (Point::new(-0.5, -0.5, 0.0), Color::white()),
+    ] {
+        assert_eq!(pattern.pattern_at(&point), color);
+    }
+}
+
+#[test]
+fn lighting_with_a_pattern_applied() {
+    let material = Material {
+        pattern: Some(Arc::new(Stripe::new(Color::white(), Color::black()))),
+        ambient: 1.0,
+        diffuse: 0.0,
+        specular: 0.0,
+        ..Material::default()
+    };
+    let eye_vector = Vec4::new(0.0, 0.0, -1.0);
+    let normal_vector = Vec4::new(0.0, 0.0, -1.0);
+    let light = Light::new(Point::new(0.0, 0.0, -10.0), Color::white());
+    let object = Sphere::default();
+    let c1 = material.lighting(
+        &object,
+        &[],
+        0.0,
+        &light,
+        &Point::new(0.9, 0.0, 0.0),
+        &eye_vector,
+        &normal_vector,
+        1.0,
+    );
+    let c2 = material.lighting(
+        &object,
+        &[],
+        0.0,
+        &light,
+        &Point::new(1.1, 0.0, 0.0),
+        &eye_vector,
+        &normal_vector,
+        1.0,
+    );
+    assert_eq!(c1, Color::white());
+    assert_eq!(c2, Color::black());
+}
+
+#[test]
+fn a_pattern_on_a_shape_in_a_transformed_group_moves_with_the_group() {
+    let mut sphere = Sphere::new(Matrix::scaling_mat_4x4(2.0, 2.0, 2.0));
+    sphere.material = Material {
+        pattern: Some(Arc::new(Stripe::new(Color::white(), Color::black()))),
+        ambient: 1.0,
+        diffuse: 0.0,
+        specular: 0.0,
+        ..Material::default()
+    };
+    let mut group = Group::new(Matrix::translation_mat_4x4(10.0, 0.0, 0.0));
+    group.add_child(Box::new(sphere));
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(0.0, 0.0, -10.0), Color::white())];
+    world.set_objects(vec![Box::new(group)]);
+    // 11 in the world is 0.5 in the sphere's object space, a white stripe
+    let ray = Ray::new(Point::new(11.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(world.color_at(&ray), Color::white());
+}
+
+#[test]
+fn materials_are_equal_only_if_they_share_the_pattern() {
+    let pattern: Arc<dyn Pattern> = Arc::new(Checkers::new(Color::white(), Color::black()));
+    let material = Material {
+        pattern: Some(pattern.clone()),
+        ..Material::default()
+    };
+    assert_eq!(material.clone(), material);
+    let other = Material {
+        pattern: Some(Arc::new(Checkers::new(Color::white(), Color::black()))),
+        ..Material::default()
+    };
+    assert_ne!(material, other);
+    assert_ne!(material, Material::default());
+    // the shape's material is what the pattern is looked up with
+    let mut sphere = Sphere::default();
+    sphere.material = material;
+    assert!(sphere.material().pattern.is_some());
+}
+
+#[test]
+fn a_nested_pattern_uses_the_sub_patterns_for_its_colors() {
+    // checkers of stripes and rings, the stripes are turned and squeezed in the checker space
+    let mut stripes = Stripe::new(Color::white(), Color::black());
+    stripes.set_transformation(Matrix::scaling_mat_4x4(0.25, 0.25, 0.25));
+    let pattern = Checkers::with_patterns(
+        Arc::new(stripes),
+        Arc::new(Ring::new(
+            Color::new(1.0, 0.0, 0.0),
+            Color::new(0.0, 0.0, 1.0),
+        )),
+    );
+    // a cube of stripes, 4 of them in one unit
+    assert_eq!(
+        pattern.pattern_at(&Point::new(0.1, 0.0, 0.5)),
+        Color::white()
+    );
+    assert_eq!(
+        pattern.pattern_at(&Point::new(0.3, 0.0, 0.5)),
+        Color::black()
+    );
+    // a cube of rings
+    assert_eq!(
+        pattern.pattern_at(&Point::new(1.5, 0.0, 0.0)),
+        Color::new(0.0, 0.0, 1.0)
+    );
+    assert_eq!(
+        pattern.pattern_at(&Point::new(-0.5, 0.0, 0.5)),
+        Color::new(1.0, 0.0, 0.0)
+    );
+}
+
+#[test]
+fn a_nested_pattern_goes_through_all_the_transformations() {
+    let object = Sphere::new(Matrix::scaling_mat_4x4(2.0, 2.0, 2.0));
+    let mut inner = test_pattern();
+    inner.set_transformation(Matrix::translation_mat_4x4(0.5, 1.0, 1.5));
+    let mut outer = Stripe::with_patterns(Arc::new(inner), Arc::new(Solid::new(Color::black())));
+    outer.set_transformation(Matrix::scaling_mat_4x4(0.5, 0.5, 0.5));
+    // world (4, 6, 7) -> object (2, 3, 3.5) -> outer (4, 6, 7) -> inner (3.5, 5, 5.5)
+    assert_eq!(
+        outer.pattern_at_shape(&object, &[], 0.0, &Point::new(4.0, 6.0, 7.0)),
+        Color::new(3.5, 5.0, 5.5)
+    );
+}
+
+#[test]
+fn a_blend_pattern_averages_two_patterns() {
+    let horizontal = Stripe::new(Color::white(), Color::new(0.0, 0.5, 0.0));
+    let mut vertical = Stripe::new(Color::white(), Color::new(0.0, 0.5, 0.0));
+    vertical.set_transformation(Matrix::rotation_y_mat_4x4(std::f64::consts::FRAC_PI_2));
+    let pattern = Blend::with_patterns(Arc::new(horizontal), Arc::new(vertical));
+    assert_eq!(
+        pattern.pattern_at(&Point::new(0.5, 0.0, -0.5)),
+        Color::white()
+    );
+    assert_eq!(
+        pattern.pattern_at(&Point::new(1.5, 0.0, -0.5)),
+        Color::new(0.5, 0.75, 0.5)
+    );
+    assert_eq!(
+        pattern.pattern_at(&Point::new(1.5, 0.0, 0.5)),
+        Color::new(0.0, 0.5, 0.0)
+    );
+}
+
+#[test]
+fn perlin_noise_is_zero_at_whole_numbers_and_smooth_between_them() {
+    for (x, y, z) in [(0.0, 0.0, 0.0), (1.0, 2.0, 3.0), (-4.0, 7.0, -1.0)] {
+        assert_eq!(perlin::noise(x, y, z), 0.0);
+    }
+    let mut values = Vec::new();
+    for i in 0..1000 {
+        let t = i as f64 * 0.0373;
+        let (x, y, z) = (t, t * 0.7 - 3.0, -t * 1.3);
+        let value = perlin::noise(x, y, z);
+        assert!((-1.0..=1.0).contains(&value));
+        // the same point always gives the same noise
+        assert_eq!(value, perlin::noise(x, y, z));
+        // and a point close by gives a noise close by
+        assert!((perlin::noise(x + 0.001, y, z) - value).abs() < 0.01);
+        values.push(value);
+    }
+    assert!(values.iter().any(|value| *value > 0.1));
+    assert!(values.iter().any(|value| *value < -0.1));
+}
+
+#[test]
+fn a_perturbed_pattern_moves_the_lookup_point() {
+    let pattern = Perturbed::new(Arc::new(test_pattern()), 0.5);
+    // no noise at whole numbers, the point is not moved
+    assert_eq!(
+        pattern.pattern_at(&Point::new(1.0, 2.0, 3.0)),
+        Color::new(1.0, 2.0, 3.0)
+    );
+    let point = Point::new(0.3, 0.6, 0.2);
+    let color = pattern.pattern_at(&point);
+    assert_ne!(color, Color::new(0.3, 0.6, 0.2));
+    // by at most scale on every axis
+    let (r, g, b) = color.rgb();
+    assert!((r - 0.3).abs() <= 0.5 && (g - 0.6).abs() <= 0.5 && (b - 0.2).abs() <= 0.5);
+    let unperturbed = Perturbed::new(Arc::new(test_pattern()), 0.0);
+    assert_eq!(unperturbed.pattern_at(&point), Color::new(0.3, 0.6, 0.2));
+}
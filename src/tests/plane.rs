@@ -0,0 +1,139 @@
+use crate::{
+    canvas::Color,
+    matrix::Matrix,
+    matters::{
+        light::Light, material::Material, plane::Plane, sphere::Sphere, Intersection, Shape,
+    },
+    ray::Ray,
+    vector::{Point, Vec4},
+    world::World,
+};
+
+#[test]
+fn default_plane_has_identity_transformation_and_default_material() {
+    let plane = Plane::default();
+    assert_eq!(plane.transformation, Matrix::identity_4x4());
+    assert_eq!(plane.material, Material::default());
+}
+
+#[test]
+fn the_normal_of_a_plane_is_constant_everywhere() {
+    let plane = Plane::default();
+    assert_eq!(
+        plane.normal_at(&Point::origin(), None),
+        Vec4::new(0.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        plane.normal_at(&Point::new(10.0, 0.0, -10.0), None),
+        Vec4::new(0.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        plane.normal_at(&Point::new(-5.0, 0.0, 150.0), None),
+        Vec4::new(0.0, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn normal_on_a_rotated_plane() {
+    let plane = Plane::new(Matrix::rotation_x_mat_4x4(std::f64::consts::FRAC_PI_2));
+    assert_eq!(
+        plane.normal_at(&Point::origin(), None),
+        Vec4::new(0.0, 0.0, 1.0)
+    );
+}
+
+#[test]
+fn intersect_with_a_ray_parallel_to_the_plane() {
+    let plane = Plane::default();
+    let ray = Ray::new(Point::new(0.0, 10.0, 0.0), Vec4::new(0.0, 0.0, 1.0));
+    assert!(plane.intersect(&ray).is_empty());
+}
+
+#[test]
+fn intersect_with_a_coplanar_ray() {
+    let plane = Plane::default();
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, 0.0, 1.0));
+    assert!(plane.intersect(&ray).is_empty());
+}
+
+#[test]
+fn a_ray_intersecting_a_plane_from_above() {
+    let plane = Plane::default();
+    let ray = Ray::new(Point::new(0.0, 1.0, 0.0), Vec4::new(0.0, -1.0, 0.0));
+    let xs = plane.intersect(&ray);
+    assert_eq!(xs.len(), 1);
+    assert_eq!(xs[0].distance, 1.0);
+    assert!(std::ptr::addr_eq(xs[0].object, &plane));
+}
+
+#[test]
+fn a_ray_intersecting_a_plane_from_below() {
+    let plane = Plane::default();
+    let ray = Ray::new(Point::new(0.0, -1.0, 0.0), Vec4::new(0.0, 1.0, 0.0));
+    let xs = plane.intersect(&ray);
+    assert_eq!(xs.len(), 1);
+    assert_eq!(xs[0].distance, 1.0);
+}
+
+#[test]
+fn a_ray_intersecting_a_translated_plane() {
+    let plane = Plane::new(Matrix::translation_mat_4x4(0.0, -2.0, 0.0));
+    let ray = Ray::new(Point::new(0.0, 1.0, 0.0), Vec4::new(0.0, -1.0, 0.0));
+    let xs = plane.intersect(&ray);
+    assert_eq!(xs[0].distance, 3.0);
+}
+
+#[test]
+fn precomputing_a_hit_on_the_underside_of_a_plane() {
+    let plane = Plane::default();
+    let ray = Ray::new(Point::new(0.0, -1.0, 0.0), Vec4::new(0.0, 1.0, 0.0));
+    let xs = plane.intersect(&ray);
+    let comps = Intersection::prepare_computation(&Intersection::hits(&xs).unwrap(), &ray, &xs);
+    assert!(comps.inside);
+    assert_eq!(comps.normalv, Vec4::new(0.0, -1.0, 0.0));
+}
+
+#[test]
+fn a_plane_in_the_world_is_shaded_and_casts_no_shadow_on_itself() {
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(0.0, 10.0, 0.0), Color::white())];
+    world.set_objects(vec![Box::new(Plane::default())]);
+    let ray = Ray::new(Point::new(0.0, 1.0, 0.0), Vec4::new(0.0, -1.0, 0.0));
+    // ambient 0.1 + diffuse 0.9 + specular 0.9, the eye is right in the reflection of the light
+    assert_eq!(world.color_at(&ray), Color::new(1.9, 1.9, 1.9));
+}
+
+#[test]
+fn a_sphere_above_a_plane_shadows_it() {
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::new(0.0, 10.0, 0.0), Color::white())];
+    world.set_objects(vec![
+        Box::new(Sphere::new(Matrix::translation_mat_4x4(0.0, 3.0, 0.0))),
+        Box::new(Plane::default()),
+    ]);
+    assert!(world.is_shadowed(&world.lights[0], &Point::new(0.0, 0.00001, 0.0)));
+    assert!(!world.is_shadowed(&world.lights[0], &Point::new(5.0, 0.00001, 0.0)));
+}
+
+#[test]
+fn the_closest_hit_among_mixed_shapes_is_shaded() {
+    // the plane sits in front of the default spheres and hides them
+    let mut world = World::default();
+    world.add_object(Box::new(Plane::new(
+        Matrix::rotation_x_mat_4x4(std::f64::consts::FRAC_PI_2)
+            .translation_mat_4x4_chain(0.0, 0.0, -3.0),
+    )));
+    let material = Material::default();
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let expected = material.lighting(
+        world.objects()[2].as_ref(),
+        &[],
+        0.0,
+        &world.lights[0],
+        &Point::new(0.0, 0.0, -3.0),
+        &Vec4::new(0.0, 0.0, -1.0),
+        &Vec4::new(0.0, 0.0, -1.0),
+        1.0,
+    );
+    assert_eq!(world.color_at(&ray), expected);
+}
@@ -1,6 +1,6 @@
 use crate::{
     matrix::Matrix,
-    matters::{sphere::Sphere, Intersectable, Intersection},
+    matters::{plane::Plane, sphere::Sphere, Intersection},
     ray::Ray,
     vector::{Point, Vec4},
 };
@@ -28,10 +28,10 @@ fn computing_a_point_from_a_distance() {
 #[test]
 fn hits_when_all_intersections_are_positive_distance() {
     let sphere = Sphere::default();
-    let intersection1 = Intersection::new(1.0, sphere.clone());
-    let intersection2 = Intersection::new(2.0, sphere.clone());
+    let intersection1 = Intersection::new(1.0, &sphere);
+    let intersection2 = Intersection::new(2.0, &sphere);
     assert_eq!(
-        Sphere::hits(&vec![intersection1.clone(), intersection2]),
+        Intersection::hits(&vec![intersection1.clone(), intersection2]),
         Some(intersection1)
     );
 }
@@ -39,10 +39,10 @@ fn hits_when_all_intersections_are_positive_distance() {
 #[test]
 fn hits_when_some_intersections_have_negative_distance() {
     let sphere = Sphere::default();
-    let intersection1 = Intersection::new(-1.0, sphere.clone());
-    let intersection2 = Intersection::new(1.0, sphere.clone());
+    let intersection1 = Intersection::new(-1.0, &sphere);
+    let intersection2 = Intersection::new(1.0, &sphere);
     assert_eq!(
-        Sphere::hits(&vec![intersection1, intersection2.clone()]),
+        Intersection::hits(&vec![intersection1, intersection2.clone()]),
         Some(intersection2)
     );
 }
@@ -50,10 +50,10 @@ fn hits_when_some_intersections_have_negative_distance() {
 #[test]
 fn hits_when_all_intersections_are_negative_distance() {
     let sphere = Sphere::default();
-    let intersection1 = Intersection::new(-1.0, sphere.clone());
-    let intersection2 = Intersection::new(-2.0, sphere.clone());
+    let intersection1 = Intersection::new(-1.0, &sphere);
+    let intersection2 = Intersection::new(-2.0, &sphere);
     assert_eq!(
-        Sphere::hits(&vec![intersection1.clone(), intersection2]),
+        Intersection::hits(&vec![intersection1.clone(), intersection2]),
         None
     );
 }
@@ -61,12 +61,12 @@ fn hits_when_all_intersections_are_negative_distance() {
 #[test]
 fn hits_is_always_the_non_negative_number() {
     let sphere = Sphere::default();
-    let intersection1 = Intersection::new(5.0, sphere.clone());
-    let intersection2 = Intersection::new(7.0, sphere.clone());
-    let intersection3 = Intersection::new(-3.0, sphere.clone());
-    let intersection4 = Intersection::new(2.0, sphere.clone());
+    let intersection1 = Intersection::new(5.0, &sphere);
+    let intersection2 = Intersection::new(7.0, &sphere);
+    let intersection3 = Intersection::new(-3.0, &sphere);
+    let intersection4 = Intersection::new(2.0, &sphere);
     assert_eq!(
-        Sphere::hits(&vec![
+        Intersection::hits(&vec![
             intersection1,
             intersection2,
             intersection3,
@@ -98,10 +98,11 @@ fn scaling_a_ray() {
 fn precomputing_the_state_of_an_intersection() {
     let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
     let sphere = Sphere::default();
-    let intersection = Intersection::new(4.0, sphere);
-    let comp = Sphere::prepare_computation(&intersection, &ray);
+    let intersection = Intersection::new(4.0, &sphere);
+    let comp =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
     assert_eq!(intersection.distance, comp.distance);
-    assert_eq!(intersection.object, comp.object);
+    assert!(std::ptr::addr_eq(intersection.object, comp.object));
     assert_eq!(Point::new(0.0, 0.0, -1.0), comp.point);
     assert_eq!(Vec4::new(0.0, 0.0, -1.0), comp.eyev);
     assert_eq!(Vec4::new(0.0, 0.0, -1.0), comp.normalv);
@@ -111,8 +112,9 @@ fn precomputing_the_state_of_an_intersection() {
 fn the_hit_when_an_intersection_occurs_on_the_outside() {
     let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
     let sphere = Sphere::default();
-    let intersection = Intersection::new(4.0, sphere);
-    let comp = Sphere::prepare_computation(&intersection, &ray);
+    let intersection = Intersection::new(4.0, &sphere);
+    let comp =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
     assert!(!comp.inside);
 }
 
@@ -120,11 +122,120 @@ fn the_hit_when_an_intersection_occurs_on_the_outside() {
 fn the_hit_when_an_intersection_occurs_on_the_inside() {
     let ray = Ray::new(Point::new(0.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 1.0));
     let sphere = Sphere::default();
-    let intersection = Intersection::new(1.0, sphere);
-    let comp = Sphere::prepare_computation(&intersection, &ray);
+    let intersection = Intersection::new(1.0, &sphere);
+    let comp =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
     assert!(comp.inside);
     assert_eq!(Point::new(0.0, 0.0, 1.0), comp.point);
     assert_eq!(Vec4::new(0.0, 0.0, -1.0), comp.eyev);
     // normal would have been (0, 0, 1), but is inverted!
     assert_eq!(Vec4::new(0.0, 0.0, -1.0), comp.normalv);
 }
+
+#[test]
+fn precomputing_the_reflection_vector() {
+    let plane = Plane::default();
+    let ray = Ray::new(
+        Point::new(0.0, 1.0, -1.0),
+        Vec4::new(0.0, -2.0f64.sqrt() / 2.0, 2.0f64.sqrt() / 2.0),
+    );
+    let intersection = Intersection::new(2.0f64.sqrt(), &plane);
+    let comp =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
+    assert_eq!(
+        comp.reflectv,
+        Vec4::new(0.0, 2.0f64.sqrt() / 2.0, 2.0f64.sqrt() / 2.0)
+    );
+}
+
+#[test]
+fn finding_n1_and_n2_at_various_intersections() {
+    let mut a = Sphere::glass();
+    a.transformation = Matrix::scaling_mat_4x4(2.0, 2.0, 2.0);
+    let mut b = Sphere::glass();
+    b.transformation = Matrix::translation_mat_4x4(0.0, 0.0, -0.25);
+    b.material.refractive_index = 2.0;
+    let mut c = Sphere::glass();
+    c.transformation = Matrix::translation_mat_4x4(0.0, 0.0, 0.25);
+    c.material.refractive_index = 2.5;
+    let ray = Ray::new(Point::new(0.0, 0.0, -4.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = vec![
+        Intersection::new(2.0, &a),
+        Intersection::new(2.75, &b),
+        Intersection::new(3.25, &c),
+        Intersection::new(4.75, &b),
+        Intersection::new(5.25, &c),
+        Intersection::new(6.0, &a),
+    ];
+    let expected = [
+        (1.0, 1.5),
+        (1.5, 2.0),
+        (2.0, 2.5),
+        (2.5, 2.5),
+        (2.5, 1.5),
+        (1.5, 1.0),
+    ];
+    for (intersection, (n1, n2)) in xs.iter().zip(expected) {
+        let comp = Intersection::prepare_computation(intersection, &ray, &xs);
+        assert_eq!((comp.n1, comp.n2), (n1, n2));
+    }
+}
+
+#[test]
+fn the_under_point_is_offset_below_the_surface() {
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let mut sphere = Sphere::glass();
+    sphere.transformation = Matrix::translation_mat_4x4(0.0, 0.0, 1.0);
+    let intersection = Intersection::new(5.0, &sphere);
+    let comp =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
+    assert!(comp.under_point.2 > 0.0);
+    assert!(comp.point.2 < comp.under_point.2);
+}
+
+#[test]
+fn the_schlick_approximation_under_total_internal_reflection() {
+    let sphere = Sphere::glass();
+    let ray = Ray::new(
+        Point::new(0.0, 0.0, 2.0f64.sqrt() / 2.0),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    let xs = vec![
+        Intersection::new(-2.0f64.sqrt() / 2.0, &sphere),
+        Intersection::new(2.0f64.sqrt() / 2.0, &sphere),
+    ];
+    let comp = Intersection::prepare_computation(&xs[1], &ray, &xs);
+    assert_eq!(comp.schlick(), 1.0);
+}
+
+#[test]
+fn the_schlick_approximation_with_a_perpendicular_viewing_angle() {
+    let sphere = Sphere::glass();
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, 1.0, 0.0));
+    let xs = vec![
+        Intersection::new(-1.0, &sphere),
+        Intersection::new(1.0, &sphere),
+    ];
+    let comp = Intersection::prepare_computation(&xs[1], &ray, &xs);
+    assert!((comp.schlick() - 0.04).abs() < 0.00001);
+}
+
+#[test]
+fn the_schlick_approximation_with_small_angle_and_n2_greater_than_n1() {
+    let sphere = Sphere::glass();
+    let ray = Ray::new(Point::new(0.0, 0.99, -2.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = vec![Intersection::new(1.8589, &sphere)];
+    let comp = Intersection::prepare_computation(&xs[0], &ray, &xs);
+    assert!((comp.schlick() - 0.48873).abs() < 0.00001);
+}
+
+#[test]
+fn a_ray_is_shot_at_time_0_unless_told_otherwise() {
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(ray.time, 0.0);
+    let ray = ray.with_time(0.25);
+    assert_eq!(ray.time, 0.25);
+    // transforming it keeps the time
+    let moved = ray.transform(Matrix::translation_mat_4x4(1.0, 2.0, 3.0));
+    assert_eq!(moved.time, 0.25);
+}
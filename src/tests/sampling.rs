@@ -0,0 +1,133 @@
+use crate::{
+    canvas::Color,
+    matters::sampling::{ApertureShape, Filter, PixelSamples, SamplePattern},
+};
+
+#[test]
+fn a_grid_puts_the_samples_on_the_centers_of_its_cells() {
+    assert_eq!(
+        SamplePattern::Grid.offsets(4, 0.5, 0),
+        vec![(-0.25, -0.25), (0.25, -0.25), (-0.25, 0.25), (0.25, 0.25)]
+    );
+    // the biggest grid which fits in the count
+    assert_eq!(SamplePattern::Grid.offsets(8, 0.5, 0).len(), 4);
+    assert_eq!(SamplePattern::Grid.offsets(9, 0.5, 0).len(), 9);
+    // at least the center
+    assert_eq!(SamplePattern::Grid.offsets(0, 0.5, 0), vec![(0.0, 0.0)]);
+}
+
+#[test]
+fn stratified_samples_are_one_in_every_cell() {
+    let offsets = SamplePattern::Stratified.offsets(4, 1.0, 42);
+    let cells = [(-1.0, -1.0), (0.0, -1.0), (-1.0, 0.0), (0.0, 0.0)];
+    for ((x, y), (cell_x, cell_y)) in offsets.iter().zip(cells) {
+        assert!(*x >= cell_x && *x < cell_x + 1.0);
+        assert!(*y >= cell_y && *y < cell_y + 1.0);
+    }
+    // the same pixel gets the same samples
+    assert_eq!(offsets, SamplePattern::Stratified.offsets(4, 1.0, 42));
+    assert_ne!(offsets, SamplePattern::Stratified.offsets(4, 1.0, 43));
+}
+
+#[test]
+fn jittered_samples_are_anywhere_within_the_radius() {
+    let offsets = SamplePattern::Jittered.offsets(7, 1.5, 3);
+    assert_eq!(offsets.len(), 7);
+    for (x, y) in offsets {
+        assert!((-1.5..1.5).contains(&x));
+        assert!((-1.5..1.5).contains(&y));
+    }
+}
+
+#[test]
+fn filters_count_nothing_past_their_radius() {
+    for filter in [
+        Filter::Box,
+        Filter::Tent,
+        Filter::Gaussian,
+        Filter::Mitchell,
+    ] {
+        let radius = filter.radius();
+        assert!(filter.weight(0.0, 0.0) > 0.0);
+        assert_eq!(filter.weight(radius + 0.01, 0.0), 0.0);
+        assert_eq!(filter.weight(0.0, -radius - 0.01), 0.0);
+    }
+}
+
+#[test]
+fn the_weight_of_a_sample_depends_on_the_filter() {
+    assert_eq!(Filter::Box.weight(0.4, -0.3), Filter::Box.weight(0.0, 0.0));
+    assert_eq!(Filter::Tent.weight(0.5, 0.0), 0.5);
+    assert_eq!(Filter::Tent.weight(0.5, 0.5), 0.25);
+    assert!(Filter::Gaussian.weight(0.5, 0.0) < Filter::Gaussian.weight(0.25, 0.0));
+    assert!((Filter::Gaussian.weight(1.5, 0.0)).abs() < 0.00001);
+    let mitchell_center = Filter::Mitchell.weight(0.0, 0.0);
+    assert!((mitchell_center - (8.0f64 / 9.0).powi(2)).abs() < 0.00001);
+    assert!((Filter::Mitchell.weight(1.0, 0.0) / mitchell_center - 1.0 / 16.0).abs() < 0.00001);
+    // the negative lobe
+    assert!(Filter::Mitchell.weight(1.5, 0.0) < 0.0);
+}
+
+#[test]
+fn pixel_samples_average_the_colors_by_their_weight() {
+    let mut samples = PixelSamples::default();
+    assert_eq!(samples.count(), 0);
+    assert_eq!(samples.color(), Color::black());
+    samples.add(&Color::white(), 3.0);
+    samples.add(&Color::black(), 1.0);
+    assert_eq!(samples.count(), 2);
+    assert_eq!(samples.color(), Color::new(0.75, 0.75, 0.75));
+    // weights which cancel out give the plain average
+    let mut samples = PixelSamples::default();
+    samples.add(&Color::white(), 1.0);
+    samples.add(&Color::black(), -1.0);
+    assert_eq!(samples.color(), Color::new(0.5, 0.5, 0.5));
+}
+
+#[test]
+fn the_variance_of_pixel_samples_is_0_when_they_agree() {
+    let mut samples = PixelSamples::default();
+    samples.add(&Color::new(0.2, 0.4, 0.6), 1.0);
+    assert_eq!(samples.variance(), 0.0);
+    samples.add(&Color::new(0.6, 0.4, 0.2), 1.0);
+    assert!(samples.variance().abs() < 0.00001);
+    // half white and half black, brightness 1 and 0 around 0.5
+    let mut samples = PixelSamples::default();
+    for _ in 0..4 {
+        samples.add(&Color::white(), 1.0);
+        samples.add(&Color::black(), 1.0);
+    }
+    assert!((samples.variance() - 0.25).abs() < 0.00001);
+}
+
+#[test]
+fn aperture_samples_are_inside_the_aperture() {
+    let hexagon = ApertureShape::Polygon {
+        sides: 6,
+        rotation: 0.0,
+    };
+    // the distance from the center to the middle of an edge
+    let apothem = (std::f64::consts::PI / 6.0).cos();
+    for i in 0..100 {
+        let (u, v) = ((i % 10) as f64 / 10.0 + 0.05, (i / 10) as f64 / 10.0 + 0.05);
+        let (x, y) = ApertureShape::Disk.sample(u, v);
+        assert!(x * x + y * y <= 1.0);
+        let (x, y) = hexagon.sample(u, v);
+        for edge in 0..6 {
+            let angle = std::f64::consts::PI / 6.0 + edge as f64 * std::f64::consts::PI / 3.0;
+            assert!(x * angle.cos() + y * angle.sin() <= apothem + 0.00001);
+        }
+    }
+    // the corners of the polygon turn with it
+    let (x, y) = hexagon.sample(1.0, 0.0);
+    assert!((x - 1.0).abs() < 0.00001 && y.abs() < 0.00001);
+    let turned = ApertureShape::Polygon {
+        sides: 6,
+        rotation: std::f64::consts::FRAC_PI_2,
+    };
+    let (x, y) = turned.sample(1.0, 0.0);
+    assert!(x.abs() < 0.00001 && (y - 1.0).abs() < 0.00001);
+    // the center is in every aperture
+    assert_eq!(ApertureShape::Disk.sample(0.0, 0.3), (0.0, 0.0));
+    assert_eq!(hexagon.sample(0.0, 0.3), (0.0, 0.0));
+}
@@ -17,8 +17,8 @@ fn default_sphere_has_identity_transformation() {
 fn a_ray_intersects_a_sphere_at_2_points() {
     let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
     let sphere = Sphere::default();
-    let intersection1 = Intersection::new(4.0, sphere.clone());
-    let intersection2 = Intersection::new(6.0, sphere.clone());
+    let intersection1 = Intersection::new(4.0, &sphere);
+    let intersection2 = Intersection::new(6.0, &sphere);
     assert_eq!(sphere.intersect(&ray), vec![intersection1, intersection2]);
 }
 
@@ -26,7 +26,7 @@ fn a_ray_intersects_a_sphere_at_2_points() {
 fn a_ray_intersects_a_sphere_at_1_points() {
     let ray = Ray::new(Point::new(0.0, 1.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
     let sphere = Sphere::default();
-    let intersect = Intersection::new(5.0, sphere.clone());
+    let intersect = Intersection::new(5.0, &sphere);
     assert_eq!(sphere.intersect(&ray), vec![intersect.clone(), intersect]);
 }
 
@@ -40,8 +40,8 @@ fn a_ray_intersects_a_sphere_at_0_points() {
 fn a_ray_originated_inside_sphere_intersects_at_two_points() {
     let ray = Ray::new(Point::new(0.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 1.0));
     let sphere = Sphere::default();
-    let intersection1 = Intersection::new(-1.0, sphere.clone());
-    let intersection2 = Intersection::new(1.0, sphere.clone());
+    let intersection1 = Intersection::new(-1.0, &sphere);
+    let intersection2 = Intersection::new(1.0, &sphere);
     assert_eq!(sphere.intersect(&ray), vec![intersection1, intersection2]);
 }
 
@@ -49,8 +49,8 @@ fn a_ray_originated_inside_sphere_intersects_at_two_points() {
 fn a_sphere_is_behide_a_ray() {
     let ray = Ray::new(Point::new(0.0, 0.0, 5.0), Vec4::new(0.0, 0.0, 1.0));
     let sphere = Sphere::default();
-    let intersection1 = Intersection::new(-6.0, sphere.clone());
-    let intersection2 = Intersection::new(-4.0, sphere.clone());
+    let intersection1 = Intersection::new(-6.0, &sphere);
+    let intersection2 = Intersection::new(-4.0, &sphere);
     assert_eq!(sphere.intersect(&ray), vec![intersection1, intersection2]);
 }
 
@@ -58,8 +58,8 @@ fn a_sphere_is_behide_a_ray() {
 fn a_sphere_is_behide_a_ray_again() {
     let ray = Ray::new(Point::new(0.0, 0.0, -2.5), Vec4::new(0.0, 0.0, 0.5));
     let sphere = Sphere::default();
-    let intersection1 = Intersection::new(3.0, sphere.clone());
-    let intersection2 = Intersection::new(7.0, sphere.clone());
+    let intersection1 = Intersection::new(3.0, &sphere);
+    let intersection2 = Intersection::new(7.0, &sphere);
     assert_eq!(sphere.intersect(&ray), vec![intersection1, intersection2]);
 }
 
@@ -78,8 +78,8 @@ fn intersecting_a_scaled_sphere_with_a_ray() {
     let mut sphere = Sphere::default();
     sphere.transformation = sphere.transformation.scaling_mat_4x4_chain(2.0, 2.0, 2.0);
     dbg!(&sphere.transformation);
-    let intersection1 = Intersection::new(3.0, sphere.clone());
-    let intersection2 = Intersection::new(7.0, sphere.clone());
+    let intersection1 = Intersection::new(3.0, &sphere);
+    let intersection2 = Intersection::new(7.0, &sphere);
     assert_eq!(sphere.intersect(&ray), vec![intersection1, intersection2]);
 }
 
@@ -97,19 +97,19 @@ fn intersecting_a_translated_sphere_with_a_ray() {
 fn normal_on_sphere_at_point_on_x_axis() {
     let s = Sphere::default();
     let p = Point::new(1.0, 0.0, 0.0);
-    assert_eq!(s.normal_at(&p), Vec4::new(1.0, 0.0, 0.0));
+    assert_eq!(s.normal_at(&p, None), Vec4::new(1.0, 0.0, 0.0));
 }
 #[test]
 fn normal_on_sphere_at_point_on_y_axis() {
     let s = Sphere::default();
     let p = Point::new(0.0, 1.0, 0.0);
-    assert_eq!(s.normal_at(&p), Vec4::new(0.0, 1.0, 0.0));
+    assert_eq!(s.normal_at(&p, None), Vec4::new(0.0, 1.0, 0.0));
 }
 #[test]
 fn normal_on_sphere_at_point_on_z_axis() {
     let s = Sphere::default();
     let p = Point::new(0.0, 0.0, 1.0);
-    assert_eq!(s.normal_at(&p), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(s.normal_at(&p, None), Vec4::new(0.0, 0.0, 1.0));
 }
 #[test]
 fn normal_on_sphere_at_nonaxial_point() {
@@ -120,7 +120,7 @@ fn normal_on_sphere_at_nonaxial_point() {
         3.0f64.sqrt() / 3.0,
     );
     assert_eq!(
-        s.normal_at(&p),
+        s.normal_at(&p, None),
         Vec4::new(
             3.0f64.sqrt() / 3.0,
             3.0f64.sqrt() / 3.0,
@@ -137,7 +137,7 @@ fn normal_is_normalized_vector() {
         3.0f64.sqrt() / 3.0,
     );
     assert_eq!(
-        s.normal_at(&p),
+        s.normal_at(&p, None),
         Vec4::new(
             3.0f64.sqrt() / 3.0,
             3.0f64.sqrt() / 3.0,
@@ -151,7 +151,7 @@ fn normal_is_normalized_vector() {
 fn normal_on_a_translated_sphere() {
     let s = Sphere::new(Matrix::translation_mat_4x4(0.0, 1.0, 0.0));
     let p = Point::new(0.0, 1.70711, -0.70711);
-    assert_eq!(s.normal_at(&p), Vec4::new(0.0, 0.70711, -0.70711,));
+    assert_eq!(s.normal_at(&p, None), Vec4::new(0.0, 0.70711, -0.70711,));
 }
 #[test]
 fn normal_on_a_transformed_sphere() {
@@ -161,7 +161,7 @@ fn normal_on_a_transformed_sphere() {
             .scaling_mat_4x4_chain(1.0, 0.5, 1.0),
     );
     let p = Point::new(0.0, 2.0f64.sqrt() / 2.0, -2.0f64.sqrt() / 2.0);
-    assert_eq!(s.normal_at(&p), Vec4::new(0.0, 0.97014, -0.24253));
+    assert_eq!(s.normal_at(&p, None), Vec4::new(0.0, 0.97014, -0.24253));
 }
 
 #[test]
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use crate::{
+    canvas::{Canvas, Color},
+    matters::{
+        pattern::Pattern,
+        sphere::Sphere,
+        texture::{CubeFace, CubeMap, TextureMap, UvCheckers, UvImage, UvMapping, UvPattern},
+    },
+    vector::Point,
+};
+
+// the color is the uv coordinate, to check the mappings
+#[derive(Debug)]
+struct UvTestPattern;
+
+impl UvPattern for UvTestPattern {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        Color::new(u, v, 0.0)
+    }
+}
+
+#[test]
+fn checkers_pattern_in_2d() {
+    let checkers = UvCheckers::new(2.0, 2.0, Color::black(), Color::white());
+    for (u, v, color) in [
+        (0.0, 0.0, Color::black()),
+        (0.5, 0.0, Color::white()),
+        (0.0, 0.5, Color::white()),
+        (0.5, 0.5, Color::black()),
+        (1.0, 1.0, Color::black()),
+    ] {
+        assert_eq!(checkers.uv_pattern_at(u, v), color);
+    }
+}
+
+#[test]
+fn using_a_spherical_mapping_on_a_3d_point() {
+    let half = 2.0f64.sqrt() / 2.0;
+    for (point, (u, v)) in [
+        (Point::new(0.0, 0.0, -1.0), (0.0, 0.5)),
+        (Point::new(1.0, 0.0, 0.0), (0.25, 0.5)),
+        (Point::new(0.0, 0.0, 1.0), (0.5, 0.5)),
+        (Point::new(-1.0, 0.0, 0.0), (0.75, 0.5)),
+        (Point::new(0.0, 1.0, 0.0), (0.5, 1.0)),
+        (Point::new(0.0, -1.0, 0.0), (0.5, 0.0)),
+        (Point::new(half, half, 0.0), (0.25, 0.75)),
+    ] {
+        let (mu, mv) = UvMapping::Spherical.map(&point);
+        assert!((mu - u).abs() < 0.00001 && (mv - v).abs() < 0.00001);
+    }
+}
+
+#[test]
+fn using_a_planar_mapping_on_a_3d_point() {
+    for (point, uv) in [
+        (Point::new(0.25, 0.0, 0.5), (0.25, 0.5)),
+        (Point::new(0.25, 0.0, -0.25), (0.25, 0.75)),
+        (Point::new(0.25, 0.5, -0.25), (0.25, 0.75)),
+        (Point::new(1.25, 0.0, 0.5), (0.25, 0.5)),
+        (Point::new(0.25, 0.0, -1.75), (0.25, 0.25)),
+        (Point::new(1.0, 0.0, -1.0), (0.0, 0.0)),
+        (Point::new(0.0, 0.0, 0.0), (0.0, 0.0)),
+    ] {
+        assert_eq!(UvMapping::Planar.map(&point), uv);
+    }
+}
+
+#[test]
+fn using_a_cylindrical_mapping_on_a_3d_point() {
+    let half = 2.0f64.sqrt() / 2.0;
+    for (point, (u, v)) in [
+        (Point::new(0.0, 0.0, -1.0), (0.0, 0.0)),
+        (Point::new(0.0, 0.5, -1.0), (0.0, 0.5)),
+        (Point::new(0.0, 1.0, -1.0), (0.0, 0.0)),
+        (Point::new(half, 0.5, -half), (0.125, 0.5)),
+        (Point::new(1.0, 0.5, 0.0), (0.25, 0.5)),
+        (Point::new(half, 0.5, half), (0.375, 0.5)),
+        (Point::new(0.0, -0.25, 1.0), (0.5, 0.75)),
+        (Point::new(-half, 0.5, half), (0.625, 0.5)),
+        (Point::new(-1.0, 1.25, 0.0), (0.75, 0.25)),
+        (Point::new(-half, 0.5, -half), (0.875, 0.5)),
+    ] {
+        let (mu, mv) = UvMapping::Cylindrical.map(&point);
+        assert!((mu - u).abs() < 0.00001 && (mv - v).abs() < 0.00001);
+    }
+}
+
+#[test]
+fn using_a_texture_map_pattern_with_a_spherical_map() {
+    let checkers = UvCheckers::new(16.0, 8.0, Color::black(), Color::white());
+    let pattern = TextureMap::new(Arc::new(checkers), UvMapping::Spherical);
+    for (point, color) in [
+        (Point::new(0.4315, 0.4670, 0.7719), Color::white()),
+        (Point::new(-0.9654, 0.2552, -0.0534), Color::black()),
+        (Point::new(0.1039, 0.7090, 0.6975), Color::white()),
+        (Point::new(-0.4986, -0.7856, -0.3663), Color::black()),
+        (Point::new(-0.0317, -0.9395, 0.3411), Color::black()),
+        (Point::new(0.4809, -0.7721, 0.4154), Color::black()),
+        (Point::new(0.0285, -0.9612, -0.2745), Color::black()),
+        (Point::new(-0.5734, -0.2162, -0.7903), Color::white()),
+        (Point::new(0.7688, -0.1470, 0.6223), Color::black()),
+        (Point::new(-0.7652, 0.2175, 0.6060), Color::black()),
+    ] {
+        assert_eq!(
+            pattern.pattern_at_shape(&Sphere::default(), &[], 0.0, &point),
+            color
+        );
+    }
+}
+
+#[test]
+fn identifying_the_face_of_a_cube_from_a_point() {
+    for (point, face) in [
+        (Point::new(-1.0, 0.5, -0.25), CubeFace::Left),
+        (Point::new(1.1, -0.75, 0.8), CubeFace::Right),
+        (Point::new(0.1, 0.6, 0.9), CubeFace::Front),
+        (Point::new(-0.7, 0.0, -2.0), CubeFace::Back),
+        (Point::new(0.5, 1.0, 0.9), CubeFace::Up),
+        (Point::new(-0.2, -1.3, 1.1), CubeFace::Down),
+    ] {
+        assert_eq!(CubeFace::from_point(&point), face);
+    }
+}
+
+#[test]
+fn uv_mapping_the_faces_of_a_cube() {
+    for (face, point, uv) in [
+        (CubeFace::Front, Point::new(-0.5, 0.5, 1.0), (0.25, 0.75)),
+        (CubeFace::Front, Point::new(0.5, -0.5, 1.0), (0.75, 0.25)),
+        (CubeFace::Back, Point::new(0.5, 0.5, -1.0), (0.25, 0.75)),
+        (CubeFace::Back, Point::new(-0.5, -0.5, -1.0), (0.75, 0.25)),
+        (CubeFace::Left, Point::new(-1.0, 0.5, -0.5), (0.25, 0.75)),
+        (CubeFace::Left, Point::new(-1.0, -0.5, 0.5), (0.75, 0.25)),
+        (CubeFace::Right, Point::new(1.0, 0.5, 0.5), (0.25, 0.75)),
+        (CubeFace::Right, Point::new(1.0, -0.5, -0.5), (0.75, 0.25)),
+        (CubeFace::Up, Point::new(-0.5, 1.0, -0.5), (0.25, 0.75)),
+        (CubeFace::Up, Point::new(0.5, 1.0, 0.5), (0.75, 0.25)),
+        (CubeFace::Down, Point::new(-0.5, -1.0, 0.5), (0.25, 0.75)),
+        (CubeFace::Down, Point::new(0.5, -1.0, -0.5), (0.75, 0.25)),
+    ] {
+        assert_eq!(face.uv(&point), uv);
+    }
+}
+
+#[test]
+fn a_cube_map_uses_the_pattern_of_the_face() {
+    let solid = |color: Color| -> Arc<dyn UvPattern> {
+        Arc::new(UvCheckers::new(1.0, 1.0, color.clone(), color))
+    };
+    let (red, green, blue) = (
+        Color::new(1.0, 0.0, 0.0),
+        Color::new(0.0, 1.0, 0.0),
+        Color::new(0.0, 0.0, 1.0),
+    );
+    let pattern = CubeMap::new(
+        solid(red.clone()),
+        solid(green.clone()),
+        solid(blue.clone()),
+        solid(Color::white()),
+        Arc::new(UvTestPattern),
+        solid(Color::black()),
+    );
+    assert_eq!(pattern.pattern_at(&Point::new(-1.0, 0.0, 0.0)), red);
+    assert_eq!(pattern.pattern_at(&Point::new(0.0, 0.0, 1.0)), green);
+    assert_eq!(pattern.pattern_at(&Point::new(1.0, 0.2, 0.0)), blue);
+    assert_eq!(
+        pattern.pattern_at(&Point::new(0.0, 0.0, -1.0)),
+        Color::white()
+    );
+    assert_eq!(
+        pattern.pattern_at(&Point::new(0.0, -1.0, 0.0)),
+        Color::black()
+    );
+    assert_eq!(
+        pattern.pattern_at(&Point::new(-0.5, 1.0, -0.5)),
+        Color::new(0.25, 0.75, 0.0)
+    );
+}
+
+#[test]
+fn an_image_is_sampled_with_bilinear_filtering() {
+    let ppm = b"P3\n2 2\n255\n255 0 0  0 255 0\n0 0 255  255 255 255\n";
+    let image = UvImage::new(Canvas::from_ppm(ppm).unwrap());
+    // corners are exactly the pixels, v = 0 is the bottom row
+    assert_eq!(image.uv_pattern_at(0.0, 1.0), Color::new(1.0, 0.0, 0.0));
+    assert_eq!(image.uv_pattern_at(1.0, 1.0), Color::new(0.0, 1.0, 0.0));
+    assert_eq!(image.uv_pattern_at(0.0, 0.0), Color::new(0.0, 0.0, 1.0));
+    assert_eq!(image.uv_pattern_at(1.0, 0.0), Color::new(1.0, 1.0, 1.0));
+    // between them the colors are blended
+    assert_eq!(image.uv_pattern_at(0.5, 1.0), Color::new(0.5, 0.5, 0.0));
+    assert_eq!(image.uv_pattern_at(0.5, 0.5), Color::new(0.5, 0.5, 0.5));
+    assert_eq!(image.uv_pattern_at(0.25, 0.0), Color::new(0.25, 0.25, 1.0));
+    assert_eq!(format!("{:?}", image), "UvImage(2x2)");
+}
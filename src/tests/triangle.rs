@@ -0,0 +1,109 @@
+use crate::{
+    matters::{smooth_triangle::SmoothTriangle, triangle::Triangle, Intersection, Shape},
+    ray::Ray,
+    vector::{Point, Vec4},
+};
+
+fn test_triangle() -> Triangle {
+    Triangle::new(
+        Point::new(0.0, 1.0, 0.0),
+        Point::new(-1.0, 0.0, 0.0),
+        Point::new(1.0, 0.0, 0.0),
+    )
+}
+
+fn test_smooth_triangle() -> SmoothTriangle {
+    SmoothTriangle::new(
+        Point::new(0.0, 1.0, 0.0),
+        Point::new(-1.0, 0.0, 0.0),
+        Point::new(1.0, 0.0, 0.0),
+        Vec4::new(0.0, 1.0, 0.0),
+        Vec4::new(-1.0, 0.0, 0.0),
+        Vec4::new(1.0, 0.0, 0.0),
+    )
+}
+
+#[test]
+fn constructing_a_triangle() {
+    let triangle = test_triangle();
+    assert_eq!(triangle.e1, Vec4::new(-1.0, -1.0, 0.0));
+    assert_eq!(triangle.e2, Vec4::new(1.0, -1.0, 0.0));
+    assert_eq!(triangle.normal, Vec4::new(0.0, 0.0, -1.0));
+}
+
+#[test]
+fn finding_the_normal_on_a_triangle() {
+    let triangle = test_triangle();
+    for point in [
+        Point::new(0.0, 0.5, 0.0),
+        Point::new(-0.5, 0.75, 0.0),
+        Point::new(0.5, 0.25, 0.0),
+    ] {
+        assert_eq!(triangle.local_normal_at(&point, None), triangle.normal);
+    }
+}
+
+#[test]
+fn intersecting_a_ray_parallel_to_the_triangle() {
+    let triangle = test_triangle();
+    let ray = Ray::new(Point::new(0.0, -1.0, -2.0), Vec4::new(0.0, 1.0, 0.0));
+    assert!(triangle.local_intersect(&ray).is_empty());
+}
+
+#[test]
+fn a_ray_misses_the_edges_of_a_triangle() {
+    let triangle = test_triangle();
+    for origin in [
+        Point::new(1.0, 1.0, -2.0),
+        Point::new(-1.0, 1.0, -2.0),
+        Point::new(0.0, -1.0, -2.0),
+    ] {
+        let ray = Ray::new(origin, Vec4::new(0.0, 0.0, 1.0));
+        assert!(triangle.local_intersect(&ray).is_empty());
+    }
+}
+
+#[test]
+fn a_ray_strikes_a_triangle() {
+    let triangle = test_triangle();
+    let ray = Ray::new(Point::new(0.0, 0.5, -2.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = triangle.local_intersect(&ray);
+    assert_eq!(xs.len(), 1);
+    assert_eq!(xs[0].distance, 2.0);
+}
+
+#[test]
+fn an_intersection_with_a_smooth_triangle_stores_u_and_v() {
+    let triangle = test_smooth_triangle();
+    let ray = Ray::new(Point::new(-0.2, 0.3, -2.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = triangle.local_intersect(&ray);
+    let (u, v) = xs[0].uv.unwrap();
+    assert!((u - 0.45).abs() < 0.00001);
+    assert!((v - 0.25).abs() < 0.00001);
+}
+
+#[test]
+fn an_intersection_without_a_triangle_has_no_u_and_v() {
+    let triangle = test_triangle();
+    assert!(Intersection::new(3.5, &triangle).uv.is_none());
+}
+
+#[test]
+fn a_smooth_triangle_uses_u_and_v_to_interpolate_the_normal() {
+    let triangle = test_smooth_triangle();
+    let intersection = Intersection::with_uv(1.0, &triangle, 0.45, 0.25);
+    assert_eq!(
+        triangle.normal_at(&Point::origin(), Some(&intersection)),
+        Vec4::new(-0.5547, 0.83205, 0.0)
+    );
+}
+
+#[test]
+fn preparing_the_normal_on_a_smooth_triangle() {
+    let triangle = test_smooth_triangle();
+    let intersection = Intersection::with_uv(1.0, &triangle, 0.45, 0.25);
+    let ray = Ray::new(Point::new(-0.2, 0.3, -2.0), Vec4::new(0.0, 0.0, 1.0));
+    let comps =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
+    assert_eq!(comps.normalv, Vec4::new(-0.5547, 0.83205, 0.0));
+}
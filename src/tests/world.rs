@@ -1,7 +1,9 @@
+use std::f64::consts::FRAC_1_SQRT_2;
+
 use crate::{
     canvas::Color,
     matrix::Matrix,
-    matters::{light::Light, sphere::Sphere, Intersectable, Intersection, Shape},
+    matters::{light::Light, plane::Plane, sphere::Sphere, Intersection, Shape},
     ray::Ray,
     vector::{Point, Vec4},
     world::World,
@@ -10,17 +12,17 @@ use crate::{
 #[test]
 fn new_world_has_no_light_source_and_no_objects() {
     let world = World::new();
-    assert!(world.light.is_none());
-    assert!(world.spheres.is_none());
+    assert!(world.lights.is_empty());
+    assert!(world.objects().is_empty());
 }
 
 #[test]
 fn default_world() {
     let world = World::default();
     let (color, diffuse, specular) = (
-        world.spheres.as_ref().unwrap()[0].material.color.clone(),
-        world.spheres.as_ref().unwrap()[0].material.diffuse,
-        world.spheres.as_ref().unwrap()[0].material.specular,
+        world.objects()[0].material().color.clone(),
+        world.objects()[0].material().diffuse,
+        world.objects()[0].material().specular,
     );
     assert_eq!(
         (color, diffuse, specular),
@@ -48,23 +50,25 @@ fn default_world_intersect() {
 fn shading_an_intersection() {
     let world = World::default();
     let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
-    let intersection = Intersection::new(4.0, world.spheres.as_ref().unwrap()[0].clone());
-    let mut comp = Sphere::prepare_computation(&intersection, &ray);
-    let c = world.shade_hits_sphere(&mut comp);
+    let intersection = Intersection::new(4.0, world.objects()[0].as_ref());
+    let comp =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
+    let c = world.shade_hits(&comp, world.max_depth);
     assert_eq!(c, Color::new(0.38066, 0.47583, 0.2855));
 }
 
 #[test]
 fn shading_an_intersection_from_inside() {
     let mut world = World::default();
-    world.light = Some(Light::new(
+    world.lights = vec![Light::new(
         Point::new(0.0, 0.25, 0.0),
         Color::new(1.0, 1.0, 1.0),
-    ));
+    )];
     let ray = Ray::new(Point::new(0.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 1.0));
-    let intersection = Intersection::new(0.5, world.spheres.as_ref().unwrap()[1].clone());
-    let mut comp = Sphere::prepare_computation(&intersection, &ray);
-    let c = world.shade_hits_sphere(&mut comp);
+    let intersection = Intersection::new(0.5, world.objects()[1].as_ref());
+    let comp =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
+    let c = world.shade_hits(&comp, world.max_depth);
     assert_eq!(c, Color::new(0.90498, 0.90498, 0.90498));
 }
 
@@ -72,43 +76,550 @@ fn shading_an_intersection_from_inside() {
 fn there_is_no_shadow_when_nothing_is_collinear_with_point_and_light() {
     let world = World::default();
     let point = Point::new(0.0, 10.0, 0.0);
-    assert_eq!(world.is_shadowed(&point), false);
+    assert_eq!(world.is_shadowed(&world.lights[0], &point), false);
 }
 
 #[test]
 fn the_shadow_when_an_object_is_between_the_point_and_the_light() {
     let world = World::default();
     let point = Point::new(10.0, -10.0, 10.0);
-    assert_eq!(world.is_shadowed(&point), true);
+    assert_eq!(world.is_shadowed(&world.lights[0], &point), true);
 }
 
 #[test]
 fn there_is_no_shadow_when_an_object_is_behind_the_light() {
     let world = World::default();
     let point = Point::new(-20.0, 20.0, -20.0);
-    assert_eq!(world.is_shadowed(&point), false);
+    assert_eq!(world.is_shadowed(&world.lights[0], &point), false);
 }
 
 #[test]
 fn there_is_no_shadow_when_an_object_is_behind_the_point() {
     let world = World::default();
     let point = Point::new(-2.0, 2.0, -2.0);
-    assert_eq!(world.is_shadowed(&point), false);
+    assert_eq!(world.is_shadowed(&world.lights[0], &point), false);
 }
 
 #[test]
 fn shade_hit_is_given_an_intersection_in_shadow() {
     let mut world = World::new();
-    world.light = Some(Light::new(
+    world.lights = vec![Light::new(
         Point::new(0.0, 0.0, -10.0),
         Color::new(1.0, 1.0, 1.0),
-    ));
+    )];
     let s1 = Sphere::default();
     let s2 = Sphere::new(Matrix::translation_mat_4x4(0.0, 0.0, 10.0));
-    world.spheres = Some(vec![s1, s2.clone()]);
+    world.set_objects(vec![Box::new(s1), Box::new(s2.clone())]);
     let ray = Ray::new(Point::new(0.0, 0.0, 0.5), Vec4::new(0.0, 0.0, 1.0));
-    let i = Intersection::new(4.0, s2);
-    let mut comps = Sphere::prepare_computation(&i, &ray);
-    let c = world.shade_hits_sphere(&mut comps);
+    let i = Intersection::new(4.0, &s2);
+    let comps = Intersection::prepare_computation(&i, &ray, std::slice::from_ref(&i));
+    let c = world.shade_hits(&comps, world.max_depth);
     assert_eq!(c, Color::new(0.1, 0.1, 0.1));
 }
+
+#[test]
+fn world_intersect_sorts_intersections_across_shape_types() {
+    let mut world = World::default();
+    world.add_object(Box::new(Plane::new(
+        Matrix::rotation_x_mat_4x4(std::f64::consts::FRAC_PI_2)
+            .translation_mat_4x4_chain(0.0, 0.0, 0.25),
+    )));
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let intersections = world.world_intersect(&ray);
+    assert_eq!(
+        intersections
+            .iter()
+            .map(|i| i.distance)
+            .collect::<Vec<f64>>(),
+        vec![4.0, 4.5, 5.25, 5.5, 6.0]
+    );
+    assert!(std::ptr::addr_eq(
+        intersections[2].object,
+        world.objects()[2].as_ref()
+    ));
+}
+
+#[test]
+fn the_reflected_color_for_a_nonreflective_material() {
+    let mut world = World::default();
+    let mut s2 = Sphere::new(Matrix::scaling_mat_4x4(0.5, 0.5, 0.5));
+    s2.material.ambient = 1.0;
+    world.objects_mut()[1] = Box::new(s2);
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, 0.0, 1.0));
+    let intersection = Intersection::new(1.0, world.objects()[1].as_ref());
+    let comps =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
+    assert_eq!(
+        world.reflected_color(&comps, world.max_depth),
+        Color::black()
+    );
+}
+
+// default world with a reflective plane below the spheres
+fn world_with_reflective_plane() -> World {
+    let mut world = World::default();
+    let mut plane = Plane::new(Matrix::translation_mat_4x4(0.0, -1.0, 0.0));
+    plane.material.reflective = 0.5;
+    world.add_object(Box::new(plane));
+    world
+}
+
+#[test]
+fn the_reflected_color_for_a_reflective_material() {
+    let world = world_with_reflective_plane();
+    let ray = Ray::new(
+        Point::new(0.0, 0.0, -3.0),
+        Vec4::new(0.0, -2.0f64.sqrt() / 2.0, 2.0f64.sqrt() / 2.0),
+    );
+    let intersection = Intersection::new(2.0f64.sqrt(), world.objects()[2].as_ref());
+    let comps =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
+    assert_eq!(
+        world.reflected_color(&comps, world.max_depth),
+        Color::new(0.19033, 0.23791, 0.14274)
+    );
+    assert_eq!(
+        world.shade_hits(&comps, world.max_depth),
+        Color::new(0.87676, 0.92434, 0.82918)
+    );
+}
+
+#[test]
+fn the_reflected_color_at_the_maximum_recursive_depth() {
+    let world = world_with_reflective_plane();
+    let ray = Ray::new(
+        Point::new(0.0, 0.0, -3.0),
+        Vec4::new(0.0, -2.0f64.sqrt() / 2.0, 2.0f64.sqrt() / 2.0),
+    );
+    let intersection = Intersection::new(2.0f64.sqrt(), world.objects()[2].as_ref());
+    let comps =
+        Intersection::prepare_computation(&intersection, &ray, std::slice::from_ref(&intersection));
+    assert_eq!(world.reflected_color(&comps, 0), Color::black());
+}
+
+#[test]
+fn color_at_with_mutually_reflective_surfaces() {
+    let mut world = World::new();
+    world.lights = vec![Light::new(Point::origin(), Color::white())];
+    let mut lower = Plane::new(Matrix::translation_mat_4x4(0.0, -1.0, 0.0));
+    lower.material.reflective = 1.0;
+    let mut upper = Plane::new(
+        Matrix::rotation_x_mat_4x4(std::f64::consts::PI).translation_mat_4x4_chain(0.0, 1.0, 0.0),
+    );
+    upper.material.reflective = 1.0;
+    world.set_objects(vec![Box::new(lower), Box::new(upper)]);
+    let ray = Ray::new(Point::origin(), Vec4::new(0.0, 1.0, 0.0));
+    // stops after max_depth bounces instead of overflowing the stack
+    let color = world.color_at(&ray);
+    assert!(color.red() > 0.0);
+}
+
+#[test]
+fn the_refracted_color_with_an_opaque_surface() {
+    let world = World::default();
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = vec![
+        Intersection::new(4.0, world.objects()[0].as_ref()),
+        Intersection::new(6.0, world.objects()[0].as_ref()),
+    ];
+    let comps = Intersection::prepare_computation(&xs[0], &ray, &xs);
+    assert_eq!(world.refracted_color(&comps, 5), Color::black());
+}
+
+// default world with the outer sphere made of glass
+fn world_with_glass_sphere() -> World {
+    let mut world = World::default();
+    let mut s1 = Sphere::glass();
+    s1.material.color = Color::new(0.8, 1.0, 0.6);
+    s1.material.diffuse = 0.7;
+    s1.material.specular = 0.2;
+    world.objects_mut()[0] = Box::new(s1);
+    world
+}
+
+#[test]
+fn the_refracted_color_at_the_maximum_recursive_depth() {
+    let world = world_with_glass_sphere();
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let xs = vec![
+        Intersection::new(4.0, world.objects()[0].as_ref()),
+        Intersection::new(6.0, world.objects()[0].as_ref()),
+    ];
+    let comps = Intersection::prepare_computation(&xs[0], &ray, &xs);
+    assert_eq!(world.refracted_color(&comps, 0), Color::black());
+}
+
+#[test]
+fn the_refracted_color_under_total_internal_reflection() {
+    let world = world_with_glass_sphere();
+    let ray = Ray::new(
+        Point::new(0.0, 0.0, 2.0f64.sqrt() / 2.0),
+        Vec4::new(0.0, 1.0, 0.0),
+    );
+    let xs = vec![
+        Intersection::new(-2.0f64.sqrt() / 2.0, world.objects()[0].as_ref()),
+        Intersection::new(2.0f64.sqrt() / 2.0, world.objects()[0].as_ref()),
+    ];
+    // inside the sphere, so the hit is the second intersection
+    let comps = Intersection::prepare_computation(&xs[1], &ray, &xs);
+    assert_eq!(world.refracted_color(&comps, 5), Color::black());
+}
+
+// default world with a glass floor below the spheres and a red ball under the floor
+fn world_with_glass_floor(reflective: f64) -> World {
+    let mut world = World::default();
+    let mut floor = Plane::new(Matrix::translation_mat_4x4(0.0, -1.0, 0.0));
+    floor.material.reflective = reflective;
+    floor.material.transparency = 0.5;
+    floor.material.refractive_index = 1.5;
+    let mut ball = Sphere::new(Matrix::translation_mat_4x4(0.0, -3.5, -0.5));
+    ball.material.color = Color::new(1.0, 0.0, 0.0);
+    ball.material.ambient = 0.5;
+    world.add_object(Box::new(floor));
+    world.add_object(Box::new(ball));
+    world
+}
+
+#[test]
+fn shade_hits_with_a_transparent_material() {
+    let world = world_with_glass_floor(0.0);
+    let ray = Ray::new(
+        Point::new(0.0, 0.0, -3.0),
+        Vec4::new(0.0, -2.0f64.sqrt() / 2.0, 2.0f64.sqrt() / 2.0),
+    );
+    let xs = vec![Intersection::new(
+        2.0f64.sqrt(),
+        world.objects()[2].as_ref(),
+    )];
+    let comps = Intersection::prepare_computation(&xs[0], &ray, &xs);
+    assert_eq!(
+        world.shade_hits(&comps, 5),
+        Color::new(0.93642, 0.68642, 0.68642)
+    );
+}
+
+#[test]
+fn shade_hits_with_a_reflective_transparent_material() {
+    let world = world_with_glass_floor(0.5);
+    let ray = Ray::new(
+        Point::new(0.0, 0.0, -3.0),
+        Vec4::new(0.0, -2.0f64.sqrt() / 2.0, 2.0f64.sqrt() / 2.0),
+    );
+    let xs = vec![Intersection::new(
+        2.0f64.sqrt(),
+        world.objects()[2].as_ref(),
+    )];
+    let comps = Intersection::prepare_computation(&xs[0], &ray, &xs);
+    assert_eq!(
+        world.shade_hits(&comps, 5),
+        Color::new(0.93391, 0.69643, 0.69243)
+    );
+}
+
+#[test]
+fn is_shadowed_tests_for_occlusion_between_two_points() {
+    let world = World::default();
+    let light_position = Point::new(-10.0, -10.0, -10.0);
+    for (point, result) in [
+        (Point::new(-10.0, -10.0, 10.0), false),
+        (Point::new(10.0, 10.0, 10.0), true),
+        (Point::new(-20.0, -20.0, -20.0), false),
+        (Point::new(-5.0, -5.0, -5.0), false),
+    ] {
+        assert_eq!(world.is_shadowed_from(&light_position, &point), result);
+    }
+}
+
+#[test]
+fn point_lights_evaluate_the_light_intensity_at_a_given_point() {
+    let world = World::default();
+    let light = &world.lights[0];
+    for (point, result) in [
+        (Point::new(0.0, 1.0001, 0.0), 1.0),
+        (Point::new(-1.0001, 0.0, 0.0), 1.0),
+        (Point::new(0.0, 0.0, -1.0001), 1.0),
+        (Point::new(0.0, 0.0, 1.0001), 0.0),
+        (Point::new(1.0001, 0.0, 0.0), 0.0),
+        (Point::new(0.0, -1.0001, 0.0), 0.0),
+        (Point::new(0.0, 0.0, 0.0), 0.0),
+    ] {
+        assert_eq!(world.intensity_at(light, &point), result);
+    }
+}
+
+#[test]
+fn area_lights_evaluate_the_light_intensity_at_a_given_point() {
+    let world = World::default();
+    let light = Light::area(
+        Point::new(-0.5, -0.5, -5.0),
+        Vec4::new(1.0, 0.0, 0.0),
+        2,
+        Vec4::new(0.0, 1.0, 0.0),
+        2,
+        Color::white(),
+    )
+    .with_jitter(false);
+    for (point, result) in [
+        (Point::new(0.0, 0.0, 2.0), 0.0),
+        (Point::new(1.0, -1.0, 2.0), 0.25),
+        (Point::new(1.5, 0.0, 2.0), 0.5),
+        (Point::new(1.25, 1.25, 3.0), 0.75),
+        (Point::new(0.0, 0.0, -2.0), 1.0),
+    ] {
+        assert_eq!(world.intensity_at(&light, &point), result);
+    }
+}
+
+#[test]
+fn lighting_uses_light_intensity_to_attenuate_color() {
+    let mut world = World::default();
+    world.lights = vec![Light::new(Point::new(0.0, 0.0, -10.0), Color::white())];
+    let mut sphere = Sphere::default();
+    sphere.material.ambient = 0.1;
+    sphere.material.diffuse = 0.9;
+    sphere.material.specular = 0.0;
+    sphere.material.color = Color::white();
+    let light = &world.lights[0];
+    for (intensity, result) in [
+        (1.0, Color::new(1.0, 1.0, 1.0)),
+        (0.5, Color::new(0.55, 0.55, 0.55)),
+        (0.0, Color::new(0.1, 0.1, 0.1)),
+    ] {
+        assert_eq!(
+            sphere.material.lighting(
+                &sphere,
+                &[],
+                0.0,
+                light,
+                &Point::new(0.0, 0.0, -1.0),
+                &Vec4::new(0.0, 0.0, -1.0),
+                &Vec4::new(0.0, 0.0, -1.0),
+                intensity,
+            ),
+            result
+        );
+    }
+}
+
+#[test]
+fn lighting_samples_the_area_light() {
+    let light = Light::area(
+        Point::new(-0.5, -0.5, -5.0),
+        Vec4::new(1.0, 0.0, 0.0),
+        2,
+        Vec4::new(0.0, 1.0, 0.0),
+        2,
+        Color::white(),
+    )
+    .with_jitter(false);
+    let mut sphere = Sphere::default();
+    sphere.material.ambient = 0.1;
+    sphere.material.diffuse = 0.9;
+    sphere.material.specular = 0.0;
+    let eye = Point::new(0.0, 0.0, -5.0);
+    for (point, result) in [
+        (
+            Point::new(0.0, 0.0, -1.0),
+            Color::new(0.9965, 0.9965, 0.9965),
+        ),
+        (
+            Point::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            Color::new(0.62318, 0.62318, 0.62318),
+        ),
+    ] {
+        let eye_vector = (&eye - &point).normalize();
+        let normal_vector = Vec4::new(point.0, point.1, point.2);
+        assert_eq!(
+            sphere.material.lighting(
+                &sphere,
+                &[],
+                0.0,
+                &light,
+                &point,
+                &eye_vector,
+                &normal_vector,
+                1.0
+            ),
+            result
+        );
+    }
+}
+
+#[test]
+fn an_area_light_casts_a_soft_shadow() {
+    // a small sphere between a floor and an area light above it
+    let mut world = World::new();
+    world.lights = vec![Light::area(
+        Point::new(-1.0, 5.0, -1.0),
+        Vec4::new(2.0, 0.0, 0.0),
+        8,
+        Vec4::new(0.0, 0.0, 2.0),
+        8,
+        Color::white(),
+    )];
+    world.set_objects(vec![
+        Box::new(Plane::default()),
+        Box::new(Sphere::new(
+            Matrix::scaling_mat_4x4(0.6, 0.6, 0.6).translation_mat_4x4_chain(0.0, 2.0, 0.0),
+        )),
+    ]);
+    let light = &world.lights[0];
+    let umbra = world.intensity_at(light, &Point::new(0.0, 0.0001, 0.0));
+    let penumbra = world.intensity_at(light, &Point::new(0.75, 0.0001, 0.0));
+    let lit = world.intensity_at(light, &Point::new(5.0, 0.0001, 0.0));
+    assert_eq!(umbra, 0.0);
+    assert!(penumbra > 0.0 && penumbra < 1.0);
+    assert_eq!(lit, 1.0);
+}
+
+#[test]
+fn a_hit_is_lit_from_the_same_samples_it_s_shadow_uses() {
+    let mut world = World::new();
+    world.lights = vec![Light::area(
+        Point::new(-1.0, 5.0, -1.0),
+        Vec4::new(2.0, 0.0, 0.0),
+        4,
+        Vec4::new(0.0, 0.0, 2.0),
+        4,
+        Color::white(),
+    )];
+    world.set_objects(vec![
+        Box::new(Plane::default()),
+        Box::new(Sphere::new(
+            Matrix::scaling_mat_4x4(0.6, 0.6, 0.6).translation_mat_4x4_chain(0.0, 2.0, 0.0),
+        )),
+    ]);
+    // straight down at the floor in the penumbra
+    let ray = Ray::new(Point::new(0.75, 1.0, 0.0), Vec4::new(0.0, -1.0, 0.0));
+    let xs = world.world_intersect(&ray);
+    let precomps = Intersection::prepare_computation(&xs[0], &ray, &xs);
+    let light = &world.lights[0];
+    let intensity = world.intensity_at(light, &precomps.over_point);
+    assert!(intensity > 0.0 && intensity < 1.0);
+    let floor = world.objects()[0].as_ref();
+    let expected = floor.material().lighting(
+        floor,
+        &[],
+        0.0,
+        light,
+        &precomps.over_point,
+        &precomps.eyev,
+        &precomps.normalv,
+        intensity,
+    );
+    assert_eq!(world.color_at(&ray), expected);
+}
+
+#[test]
+fn shadows_from_a_directional_light_go_on_to_infinity() {
+    let world = World::default();
+    let sun = Light::directional(Vec4::new(0.0, -1.0, 0.0), Color::white());
+    assert_eq!(world.intensity_at(&sun, &Point::new(0.0, 1.0001, 0.0)), 1.0);
+    assert_eq!(
+        world.intensity_at(&sun, &Point::new(0.0, -1.0001, 0.0)),
+        0.0
+    );
+    // the sphere is far below the sun, a point light at the same height would not be blocked
+    assert_eq!(world.intensity_at(&sun, &Point::new(0.0, -100.0, 0.0)), 0.0);
+    assert_eq!(world.intensity_at(&sun, &Point::new(5.0, -100.0, 0.0)), 1.0);
+}
+
+#[test]
+fn a_light_can_be_any_kind_of_light() {
+    let mut world = World::default();
+    world.lights = vec![Light::spot(
+        Point::new(-10.0, 10.0, -10.0),
+        Vec4::new(1.0, -1.0, 1.0),
+        0.1,
+        0.2,
+        Color::white(),
+    )];
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let spot = world.color_at(&ray);
+    world.lights = vec![Light::new(Point::new(-10.0, 10.0, -10.0), Color::white())];
+    // the spot points at the spheres, so it lights them like a point light
+    assert_eq!(spot, world.color_at(&ray));
+    world.lights = vec![Light::directional(
+        Vec4::new(0.0, 0.0, -1.0),
+        Color::white(),
+    )];
+    // light from behind the sphere, only ambient
+    assert_eq!(world.color_at(&ray), Color::new(0.08, 0.1, 0.06));
+}
+
+#[test]
+fn a_point_can_be_lit_by_one_light_and_in_the_shadow_of_another() {
+    let mut world = World::default();
+    world.lights = vec![
+        Light::new(Point::new(0.0, 0.0, -10.0), Color::white()),
+        Light::new(Point::new(0.0, 0.0, 10.0), Color::white()),
+    ];
+    // the spheres are behind the point for the first light and in front of it for the second one
+    let point = Point::new(0.0, 0.0, -1.0001);
+    assert!(!world.is_shadowed(&world.lights[0], &point));
+    assert!(world.is_shadowed(&world.lights[1], &point));
+}
+
+#[test]
+fn shade_hits_adds_up_the_lights_shadowed_on_their_own() {
+    let mut world = World::new();
+    world.lights = vec![
+        Light::new(Point::new(0.0, 0.0, -10.0), Color::white()),
+        Light::new(Point::new(-10.0, 0.0, -10.0), Color::new(0.5, 0.5, 0.5)),
+    ];
+    let sphere = Sphere::default();
+    // only blocks the second light
+    let blocker = Sphere::new(
+        Matrix::scaling_mat_4x4(0.5, 0.5, 0.5).translation_mat_4x4_chain(-5.0, 0.0, -5.5),
+    );
+    world.set_objects(vec![Box::new(sphere.clone()), Box::new(blocker)]);
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    let (point, eyev, normalv) = (
+        Point::new(0.0, 0.0, -1.0),
+        Vec4::new(0.0, 0.0, -1.0),
+        Vec4::new(0.0, 0.0, -1.0),
+    );
+    let lit = sphere.material.lighting(
+        &sphere,
+        &[],
+        0.0,
+        &world.lights[0],
+        &point,
+        &eyev,
+        &normalv,
+        1.0,
+    );
+    let shadowed = sphere.material.lighting(
+        &sphere,
+        &[],
+        0.0,
+        &world.lights[1],
+        &point,
+        &eyev,
+        &normalv,
+        0.0,
+    );
+    assert_eq!(world.color_at(&ray), lit.clone() + shadowed);
+    // without the blocker both lights reach the point
+    world.objects_mut().pop();
+    let both = lit
+        + sphere.material.lighting(
+            &sphere,
+            &[],
+            0.0,
+            &world.lights[1],
+            &point,
+            &eyev,
+            &normalv,
+            1.0,
+        );
+    assert_eq!(world.color_at(&ray), both);
+}
+
+#[test]
+fn a_world_without_lights_is_dark() {
+    let mut world = World::default();
+    world.lights.clear();
+    let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vec4::new(0.0, 0.0, 1.0));
+    assert_eq!(world.color_at(&ray), Color::black());
+}
@@ -1,38 +1,51 @@
 use std::io::Write;
 
 use crate::{
+    bvh::Bvh,
     canvas::Color,
     matrix::Matrix,
     matters::{
-        camera::Camera, light::Light, material::Material, sphere::Sphere, Intersectable,
-        Intersection, PrerareComputation, Shape,
+        background::Background, camera::Camera, group::Group, light::Light, material::Material,
+        plane::Plane, sphere::Sphere, Intersection, PrerareComputation, Shape,
     },
     ray::Ray,
     vector::{Point, Vec4},
 };
 
 pub struct World {
-    pub light: Option<Light>,
-    pub otherlights: Option<Vec<Light>>,
-    pub spheres: Option<Vec<Sphere>>,
+    // every light is shaded and shadow tested on its own, their contributions add up
+    pub lights: Vec<Light>,
+    // every kind of shape in the scene (spheres, planes, ...), only changed through methods which drop the bvh
+    objects: Vec<Box<dyn Shape>>,
+    // built with build_bvh, dropped whenever objects change so an outdated tree is never used
+    bvh: Option<Bvh>,
+    // how many times a ray can bounce off reflective (or through transparent) surfaces, two mirrors facing each other would bounce forever
+    pub max_depth: usize,
+    // color of the rays which don't hit anything
+    pub background: Background,
 }
 
+// default for max_depth
+const MAX_DEPTH: usize = 5;
+
 impl Default for World {
     fn default() -> Self {
         // defalt world will have a light source and two concentric spheres
         let light = Light::new(Point::new(-10.0, 10.0, -10.0), Color::white());
-        let mut spheres = Vec::new();
+        let mut objects: Vec<Box<dyn Shape>> = Vec::new();
         let mut s1 = Sphere::default();
         s1.material.color = Color::new(0.8, 1.0, 0.6);
         s1.material.diffuse = 0.7;
         s1.material.specular = 0.2;
-        spheres.push(s1);
+        objects.push(Box::new(s1));
         let s2 = Sphere::new(Matrix::scaling_mat_4x4(0.5, 0.5, 0.5));
-        spheres.push(s2);
+        objects.push(Box::new(s2));
         Self {
-            light: Some(light),
-            spheres: Some(spheres),
-            otherlights: None,
+            lights: vec![light],
+            objects,
+            bvh: None,
+            max_depth: MAX_DEPTH,
+            background: Background::default(),
         }
     }
 }
@@ -40,111 +53,194 @@ impl Default for World {
 impl World {
     pub fn new() -> Self {
         Self {
-            light: None,
-            spheres: None,
-            otherlights: None,
+            lights: Vec::new(),
+            objects: Vec::new(),
+            bvh: None,
+            max_depth: MAX_DEPTH,
+            background: Background::default(),
         }
     }
-    pub fn world_intersect(&self, ray: &Ray) -> Vec<Intersection<Sphere>> {
-        let mut xs = self
-            .spheres
-            .as_ref()
-            .unwrap()
-            .iter()
-            .map(|sphere| sphere.intersect(&ray))
-            .flatten()
-            .collect::<Vec<Intersection<Sphere>>>();
+
+    pub fn objects(&self) -> &[Box<dyn Shape>] {
+        &self.objects
+    }
+
+    // the objects can be changed in any way through it, so the bvh is dropped
+    pub fn objects_mut(&mut self) -> &mut Vec<Box<dyn Shape>> {
+        self.bvh = None;
+        &mut self.objects
+    }
+
+    pub fn set_objects(&mut self, objects: Vec<Box<dyn Shape>>) {
+        self.bvh = None;
+        self.objects = objects;
+    }
+
+    pub fn add_object(&mut self, object: Box<dyn Shape>) {
+        self.bvh = None;
+        self.objects.push(object);
+    }
+
+    // builds a bounding volume hierarchy over the objects so a ray is only tested against the objects near it
+    pub fn build_bvh(&mut self) {
+        self.bvh = Some(Bvh::build(&self.objects));
+    }
+
+    // intersections of the ray with every object in the world, sorted by distance
+    pub fn world_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut xs = match self.bvh.as_ref() {
+            Some(bvh) => {
+                // every change to the objects drops the tree, one built for other objects would look up the wrong ones
+                debug_assert_eq!(bvh.len(), self.objects.len());
+                bvh.intersect(&self.objects, ray)
+            }
+            None => self
+                .objects
+                .iter()
+                .flat_map(|object| object.intersect(ray))
+                .collect::<Vec<Intersection>>(),
+        };
         xs.sort();
         xs
     }
 
-    pub fn shade_hits_sphere(&self, precomps: &mut PrerareComputation<Sphere>) -> Color {
-        let is_shadowed = self.is_shadowed(precomps.over_point.as_ref());
-        let mut col = precomps.object.material.clone().lighting(
-            self.light.as_ref().unwrap(),
-            &precomps.point,
-            &precomps.eyev,
-            &precomps.normalv,
-            is_shadowed,
-        );
-        if let Some(lights) = self.otherlights.as_ref() {
-            for light in lights {
-                col = col
-                    + precomps.object.material.lighting(
-                        &light,
-                        &precomps.point,
-                        &precomps.eyev,
-                        &precomps.normalv,
-                        is_shadowed,
-                    );
-            }
+    // remaining is how many more bounces are allowed for the reflected ray
+    pub fn shade_hits(&self, precomps: &PrerareComputation, remaining: usize) -> Color {
+        let material = precomps.object.material();
+        // a point can be lit by one light and in the shadow of another
+        // the jittered samples of an area light are seeded with the point, it's lit from the same samples it's shadow is looked up from
+        let col = self.lights.iter().fold(Color::black(), |col, light| {
+            col + material.lighting(
+                precomps.object,
+                &precomps.parents,
+                precomps.time,
+                light,
+                &precomps.over_point,
+                &precomps.eyev,
+                &precomps.normalv,
+                self.intensity_at_time(light, &precomps.over_point, precomps.time),
+            )
+        });
+        let reflected = self.reflected_color(precomps, remaining);
+        let refracted = self.refracted_color(precomps, remaining);
+        if material.reflective > 0.0 && material.transparency > 0.0 {
+            // glass like surfaces reflect more light the more grazing the angle is
+            let reflectance = precomps.schlick();
+            col + reflectance * reflected + (1.0 - reflectance) * refracted
+        } else {
+            col + reflected + refracted
         }
-        col
     }
 
-    pub fn color_at_sphere(&self, ray: &Ray) -> Color {
-        let intersections = self.world_intersect(ray);
-        if let Some(hit) = Sphere::hits(&intersections) {
-            let mut precomps = Sphere::prepare_computation(&hit, ray);
-            self.shade_hits_sphere(&mut precomps)
-        } else {
-            Color::black()
+    // color seen in the reflection at the hit, scaled by how reflective the surface is
+    pub fn reflected_color(&self, precomps: &PrerareComputation, remaining: usize) -> Color {
+        let reflective = precomps.object.material().reflective;
+        if remaining == 0 || reflective == 0.0 {
+            return Color::black();
         }
+        // start from over_point so the reflected ray doesn't hit the surface it starts on
+        let reflect_ray = Ray::new(precomps.over_point.clone(), precomps.reflectv.clone())
+            .with_time(precomps.time);
+        reflective * self.color_at_depth(&reflect_ray, remaining - 1)
     }
 
-    pub fn is_shadowed(&self, point: &Point) -> bool {
-        if let Some(light) = self.light.as_ref() {
-            let v_from_light_to_point = &light.position - point;
-            let distance = v_from_light_to_point.magnitude();
-            let v_from_light_to_point_normalized = v_from_light_to_point.normalize();
-            let ray = Ray::new(point.clone(), v_from_light_to_point_normalized.clone());
-            let intersections = self.world_intersect(&ray);
-            if let Some(hit) = Sphere::hits(&intersections) {
-                if hit.distance < distance {
-                    return true;
-                }
-            }
+    // color seen through the hit, scaled by how transparent the surface is
+    pub fn refracted_color(&self, precomps: &PrerareComputation, remaining: usize) -> Color {
+        let transparency = precomps.object.material().transparency;
+        if remaining == 0 || transparency == 0.0 {
+            return Color::black();
         }
-        // check for other light sources (if there are any)
-        if let Some(lights) = self.otherlights.as_ref() {
-            for light in lights {
-                let v_from_light_to_point = point - &light.position;
-                let distance = v_from_light_to_point.magnitude();
-                let v_from_light_to_point_normalized = v_from_light_to_point.normalize();
-                let ray = Ray::new(point.clone(), v_from_light_to_point_normalized.clone());
-                let intersections = self.world_intersect(&ray);
-                if let Some(hit) = Sphere::hits(&intersections) {
-                    if hit.distance < distance {
-                        return true;
-                    }
-                }
-            }
+        // snell's law, sin(theta_i) * n1 = sin(theta_t) * n2
+        let n_ratio = precomps.n1 / precomps.n2;
+        let cos_i = precomps.eyev.dot(&precomps.normalv);
+        let sin2_t = n_ratio.powi(2) * (1.0 - cos_i.powi(2));
+        // total internal reflection, no light gets through
+        if sin2_t > 1.0 {
+            return Color::black();
         }
-        false
+        let cos_t = (1.0 - sin2_t).sqrt();
+        let direction =
+            &((n_ratio * cos_i - cos_t) * &precomps.normalv) - &(n_ratio * &precomps.eyev);
+        // start from under_point so the refracted ray doesn't hit the surface it starts on
+        let refract_ray =
+            Ray::new(precomps.under_point.clone(), direction).with_time(precomps.time);
+        transparency * self.color_at_depth(&refract_ray, remaining - 1)
+    }
+
+    pub fn color_at(&self, ray: &Ray) -> Color {
+        self.color_at_depth(ray, self.max_depth)
+    }
+
+    // like color_at, but the reflected rays can only bounce remaining more times
+    pub fn color_at_depth(&self, ray: &Ray, remaining: usize) -> Color {
+        let intersections = self.world_intersect(ray);
+        if let Some(hit) = Intersection::hits(&intersections) {
+            let precomps = Intersection::prepare_computation(&hit, ray, &intersections);
+            self.shade_hits(&precomps, remaining)
+        } else {
+            self.background.color_for(&ray.direction)
+        }
+    }
+
+    // distance of the closest object in front of the ray, if any
+    fn closest_hit_distance(&self, ray: &Ray) -> Option<f64> {
+        Intersection::hits(&self.world_intersect(ray)).map(|hit| hit.distance)
+    }
+
+    // true if something is between the point and the light position
+    pub fn is_shadowed_from(&self, light_position: &Point, point: &Point) -> bool {
+        let v_from_point_to_light = light_position - point;
+        let distance = v_from_point_to_light.magnitude();
+        let ray = Ray::new(point.clone(), v_from_point_to_light.normalize());
+        self.closest_hit_distance(&ray)
+            .is_some_and(|hit_distance| hit_distance < distance)
+    }
+
+    // fraction of the light which reaches the point, 0 or 1 for a point light and anything between for an area light
+    pub fn intensity_at(&self, light: &Light, point: &Point) -> f64 {
+        self.intensity_at_time(light, point, 0.0)
+    }
+
+    // like intensity_at, with the moving objects where they are at the time
+    pub fn intensity_at_time(&self, light: &Light, point: &Point, time: f64) -> f64 {
+        let light_vectors = light.light_vectors(point);
+        let lit = light_vectors
+            .iter()
+            .filter(|(direction, distance)| {
+                // for a directional light the distance is infinite, anything in the way casts a shadow
+                let ray = Ray::new(point.clone(), direction.clone()).with_time(time);
+                self.closest_hit_distance(&ray)
+                    .is_none_or(|hit_distance| hit_distance >= *distance)
+            })
+            .count();
+        lit as f64 / light_vectors.len() as f64
+    }
+
+    // true if no light at all reaches the point from the light (works for every kind of light)
+    pub fn is_shadowed(&self, light: &Light, point: &Point) -> bool {
+        self.intensity_at(light, point) == 0.0
     }
 
     pub fn world_to_ppm(&mut self) {
-        let mut floor = Sphere::default();
-        floor.transformation = Matrix::scaling_mat_4x4(10.0, 0.01, 10.0);
-        floor.material = Material::default();
+        let mut floor = Plane::default();
         floor.material.color = Color::new(1.0, 0.9, 0.9);
         floor.material.specular = 0.0;
 
-        let mut left_wall = Sphere::default();
-        left_wall.transformation = Matrix::identity_4x4()
-            .scaling_mat_4x4_chain(10.0, 0.01, 10.0)
-            .rotation_x_mat_4x4_chain(std::f64::consts::FRAC_PI_2)
-            .rotation_y_mat_4x4_chain(-std::f64::consts::FRAC_PI_4)
-            .translation_mat_4x4_chain(0.0, 0.0, 5.0);
-        left_wall.material = floor.material.clone();
-
-        let mut right_wall = Sphere::default();
-        right_wall.transformation = Matrix::identity_4x4()
-            .scaling_mat_4x4_chain(10.0, 0.01, 10.0)
-            .rotation_x_mat_4x4_chain(std::f64::consts::FRAC_PI_2)
-            .rotation_y_mat_4x4_chain(std::f64::consts::FRAC_PI_4)
-            .translation_mat_4x4_chain(0.0, 0.0, 5.0);
-        right_wall.material = floor.material.clone();
+        let left_wall = Plane {
+            transformation: Matrix::identity_4x4()
+                .rotation_x_mat_4x4_chain(std::f64::consts::FRAC_PI_2)
+                .rotation_y_mat_4x4_chain(-std::f64::consts::FRAC_PI_4)
+                .translation_mat_4x4_chain(0.0, 0.0, 5.0),
+            material: floor.material.clone(),
+        };
+
+        let right_wall = Plane {
+            transformation: Matrix::identity_4x4()
+                .rotation_x_mat_4x4_chain(std::f64::consts::FRAC_PI_2)
+                .rotation_y_mat_4x4_chain(std::f64::consts::FRAC_PI_4)
+                .translation_mat_4x4_chain(0.0, 0.0, 5.0),
+            material: floor.material.clone(),
+        };
 
         let mut middle = Sphere::default();
         middle.transformation = Matrix::translation_mat_4x4(-0.5, 1.0, 0.5);
@@ -168,8 +264,15 @@ impl World {
         left.material.diffuse = 0.7;
         left.material.specular = 0.3;
 
-        self.light = Some(Light::new(Point::new(-10.0, 10.0, -10.0), Color::white()));
-        self.spheres = Some(vec![floor, left_wall, right_wall, middle, right, left]);
+        self.lights = vec![Light::new(Point::new(-10.0, 10.0, -10.0), Color::white())];
+        self.set_objects(vec![
+            Box::new(floor),
+            Box::new(left_wall),
+            Box::new(right_wall),
+            Box::new(middle),
+            Box::new(right),
+            Box::new(left),
+        ]);
         let mut camera = Camera::new(300, 150, std::f64::consts::FRAC_PI_3);
 
         camera.transform = Matrix::view_transformation(
@@ -189,8 +292,8 @@ impl World {
 
     // working on this
     pub fn shadow_dog_to_ppm(&mut self) {
-        let mut background = Sphere::default();
-        background.transformation = Matrix::scaling_mat_4x4(10.0, 10.0, 0.01)
+        let mut background = Plane::default();
+        background.transformation = Matrix::rotation_x_mat_4x4(std::f64::consts::FRAC_PI_2)
             .rotation_y_mat_4x4_chain(std::f64::consts::FRAC_PI_4)
             .translation_mat_4x4_chain(3.0, 0.0, 3.0);
         background.material.color = Color::new(0.3, 0.3, 0.3);
@@ -221,8 +324,15 @@ impl World {
             .translation_mat_4x4_chain(0.0, -2.0, 0.0);
         f3.material = s1.material.clone();
 
-        self.light = Some(Light::new(Point::new(-10.0, 0.0, -5.0), Color::white()));
-        self.spheres = Some(vec![s1, s2, f1, f2, f3, background]);
+        // the parts of the dog are placed relative to each other, the group places the whole dog in the world
+        let mut dog = Group::default();
+        dog.add_child(Box::new(s2));
+        dog.add_child(Box::new(f1));
+        dog.add_child(Box::new(f2));
+        dog.add_child(Box::new(f3));
+
+        self.lights = vec![Light::new(Point::new(-10.0, 0.0, -5.0), Color::white())];
+        self.set_objects(vec![Box::new(s1), Box::new(dog), Box::new(background)]);
         let mut camera = Camera::new(300, 150, std::f64::consts::FRAC_PI_3);
 
         camera.transform = Matrix::view_transformation(
@@ -239,4 +349,44 @@ impl World {
         let mut file = std::fs::OpenOptions::new().write(true).open(path).unwrap();
         file.write_all(image.to_ppm().as_bytes()).unwrap();
     }
+
+    // benchmark for the bounding volume hierarchy, thousands of small spheres over a floor
+    pub fn many_spheres_to_ppm(&mut self) {
+        let mut floor = Plane::default();
+        floor.material.color = Color::new(0.9, 0.9, 0.9);
+        floor.material.specular = 0.0;
+        self.set_objects(vec![Box::new(floor)]);
+
+        // 64 x 64 spheres on a grid, the color changes across the grid so every sphere is different
+        let count = 64;
+        for row in 0..count {
+            for col in 0..count {
+                let (x, z) = (col as f64 - count as f64 / 2.0, row as f64);
+                let mut sphere = Sphere::new(
+                    Matrix::scaling_mat_4x4(0.3, 0.3, 0.3).translation_mat_4x4_chain(x, 0.3, z),
+                );
+                sphere.material.color =
+                    Color::new(col as f64 / count as f64, 0.5, row as f64 / count as f64);
+                self.add_object(Box::new(sphere));
+            }
+        }
+        self.lights = vec![Light::new(Point::new(-10.0, 10.0, -10.0), Color::white())];
+
+        self.build_bvh();
+
+        let mut camera = Camera::new(300, 150, std::f64::consts::FRAC_PI_3);
+        camera.transform = Matrix::view_transformation(
+            Point::new(0.0, 6.0, -10.0),
+            Point::new(0.0, 0.0, 20.0),
+            Vec4::new(0.0, 1.0, 0.0),
+        );
+
+        let image = camera.render(self);
+        let path = std::path::Path::new(".\\many_spheres.ppm");
+        if !path.exists() {
+            std::fs::File::create(path).unwrap();
+        }
+        let mut file = std::fs::OpenOptions::new().write(true).open(path).unwrap();
+        file.write_all(image.to_ppm().as_bytes()).unwrap();
+    }
 }